@@ -0,0 +1,181 @@
+use crate::commands::get_pwm_conf::PwmStatus;
+use crate::commands::{ControlledBy, SetDbcConf, SetDbcTh, SetPwmConf};
+use crate::pll::Clocks;
+pub use crate::commands::set_dbc_conf::EnergySaving;
+pub use crate::commands::set_pwm_conf::BrightnessPrescaler;
+
+/// System clock cycles in one period of the PWM signal for a frequency divider of 0
+const PWM_PERIOD_CYCLES: u32 = 256 * 256;
+/// Largest value of the 17-bit DBC thresholds
+const DBC_TH_MAX: u32 = 0x1_FFFF;
+
+/// Backlight PWM output settings, see `set_pwm_conf` (0xBE)
+///
+/// `Backlight::default()` has the PWM output off, as the controller comes out of reset.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Backlight {
+    /// PWM frequency divider PWMF, the PWM signal frequency is `system clock / (256 * (PWMF + 1)) / 256`
+    pub pwm_frequency: u8,
+    /// PWM duty cycle, 0 is off and 255 fully on
+    pub brightness: u8,
+    pub enabled: bool,
+}
+
+impl Backlight {
+    /// Frequency divider giving the PWM signal frequency closest to `frequency` Hz
+    pub const fn pwm_frequency_for(clocks: &Clocks, frequency: u32) -> u8 {
+        if frequency == 0 {
+            return u8::MAX;
+        }
+        let period = PWM_PERIOD_CYCLES as u64 * frequency as u64;
+        let divider = (clocks.system_clock() as u64 + period / 2) / period;
+        if divider == 0 {
+            0
+        } else if divider > 256 {
+            u8::MAX
+        } else {
+            (divider - 1) as u8
+        }
+    }
+
+    /// Achieved PWM signal frequency in Hz
+    pub const fn output_frequency(&self, clocks: &Clocks) -> u32 {
+        clocks.system_clock() / PWM_PERIOD_CYCLES / (self.pwm_frequency as u32 + 1)
+    }
+
+    /// PWM configuration with `dbc` applied, the duty cycle is left to DBC while it's on
+    pub(crate) fn pwm_conf(&self, dbc: &Dbc) -> SetPwmConf {
+        let pwm_control = if dbc.energy_saving != EnergySaving::DbcDisable {
+            ControlledBy::Lcdc
+        } else {
+            ControlledBy::Host
+        };
+        SetPwmConf {
+            pwm_frequency: self.pwm_frequency,
+            pwm_duty_cycle: self.brightness,
+            pwm_control,
+            pwm_enable: self.enabled,
+            dbc_manual_brightness: 0,
+            dbc_minimum_brightness: dbc.minimum_brightness,
+            brightness_prescaler: dbc.brightness_prescaler,
+        }
+    }
+}
+
+/// Dynamic backlight control, dims the backlight PWM output according to the image content,
+/// see `set_dbc_conf` (0xD0) and `set_dbc_th` (0xD4)
+///
+/// Starts out disabled, the power-on setting of `set_dbc_conf`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dbc {
+    /// How aggressively the backlight is dimmed, `EnergySaving::DbcDisable` turns DBC off
+    pub energy_saving: EnergySaving,
+    /// Changes the brightness gradually to avoid visible flickering
+    pub transition_effect: bool,
+    /// Brightness DBC never dims below
+    pub minimum_brightness: u8,
+    /// How gradually the brightness changes with `transition_effect`,
+    /// the iteration ratio is `system clock / prescaler / 32768`
+    pub brightness_prescaler: BrightnessPrescaler,
+}
+
+impl Default for Dbc {
+    fn default() -> Self {
+        Self {
+            energy_saving: EnergySaving::DbcDisable,
+            transition_effect: false,
+            minimum_brightness: 0,
+            brightness_prescaler: BrightnessPrescaler::Off,
+        }
+    }
+}
+
+impl Dbc {
+    pub(crate) fn dbc_conf(&self) -> SetDbcConf {
+        SetDbcConf {
+            manual_brightness_enable: false,
+            transition_effect_enable: self.transition_effect,
+            energy_saving: self.energy_saving,
+            master_enable: self.energy_saving != EnergySaving::DbcDisable,
+        }
+    }
+
+    /// Thresholds of the conservative, normal and aggressive modes for a `width` x `height` screen,
+    /// 10%, 25% and 60% of `width * height * 3 / 16`, limited to what the registers hold for the largest panels
+    pub(crate) fn thresholds(width: u16, height: u16) -> SetDbcTh {
+        let pixels = u32::from(width) * u32::from(height) * 3;
+        SetDbcTh {
+            dbc_th1: (pixels / 160).min(DBC_TH_MAX).into(),
+            dbc_th2: (pixels / 64).min(DBC_TH_MAX).into(),
+            dbc_th3: (pixels * 6 / 160).min(DBC_TH_MAX).into(),
+        }
+    }
+}
+
+impl From<PwmStatus> for Backlight {
+    fn from(status: PwmStatus) -> Self {
+        Self {
+            pwm_frequency: status.pwm_frequency,
+            brightness: status.pwm_duty_cycle,
+            enabled: status.pwm_enable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn frequency() {
+        let clocks = Clocks::new(10_000_000, 100_000_000, 30_000_000).unwrap();
+        assert_eq!(Backlight::pwm_frequency_for(&clocks, 300), 4);
+        assert_eq!(Backlight::pwm_frequency_for(&clocks, 100_000), 0);
+        assert_eq!(Backlight::pwm_frequency_for(&clocks, 1), 255);
+        assert_eq!(Backlight::pwm_frequency_for(&clocks, 0), 255);
+        let backlight = Backlight {
+            pwm_frequency: 4,
+            ..Backlight::default()
+        };
+        assert_eq!(backlight.output_frequency(&clocks), 305);
+    }
+
+    #[test]
+    fn pwm_conf() {
+        let backlight = Backlight {
+            pwm_frequency: 6,
+            brightness: 0xF0,
+            enabled: true,
+        };
+        assert_eq!(backlight.pwm_conf(&Dbc::default()).pack().unwrap(), [6, 0xF0, 0b0001, 0, 0, 0]);
+        assert_eq!(Backlight::default().pwm_conf(&Dbc::default()).pack().unwrap(), [0; 6]);
+        let dbc = Dbc {
+            minimum_brightness: 0x20,
+            brightness_prescaler: BrightnessPrescaler::B4,
+            ..Dbc::default()
+        };
+        assert_eq!(backlight.pwm_conf(&dbc).pack().unwrap(), [6, 0xF0, 0b0001, 0, 0x20, 0b0100]);
+        let dbc = Dbc {
+            energy_saving: EnergySaving::ConservativeMode,
+            ..dbc
+        };
+        assert_eq!(backlight.pwm_conf(&dbc).pack().unwrap(), [6, 0xF0, 0b1001, 0, 0x20, 0b0100]);
+    }
+
+    #[test]
+    fn dbc() {
+        let th = Dbc::thresholds(800, 480);
+        assert_eq!((*th.dbc_th1, *th.dbc_th2, *th.dbc_th3), (7200, 18000, 43200));
+        let th = Dbc::thresholds(2048, 2048);
+        assert_eq!((*th.dbc_th1, *th.dbc_th2, *th.dbc_th3), (78643, DBC_TH_MAX, DBC_TH_MAX));
+        assert!(th.pack().is_ok());
+        assert_eq!(Dbc::default().dbc_conf().pack().unwrap(), [0]);
+        let dbc = Dbc {
+            energy_saving: EnergySaving::AgressiveMode,
+            transition_effect: true,
+            ..Dbc::default()
+        };
+        assert_eq!(dbc.dbc_conf().pack().unwrap(), [0b0010_1101]);
+    }
+}
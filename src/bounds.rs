@@ -53,7 +53,7 @@ impl Bounds {
     {
         let x_start = match x.start_bound() {
             Bound::Included(&v) => v,
-            Bound::Excluded(&v) => v + 1,
+            Bound::Excluded(&v) => v.checked_add(1).ok_or(OutOfBoundsError)?,
             Bound::Unbounded => 0,
         };
         if x_start < parent.x_start {
@@ -61,7 +61,7 @@ impl Bounds {
         }
         let x_end = match x.end_bound() {
             Bound::Included(&v) => v,
-            Bound::Excluded(&v) => v - 1,
+            Bound::Excluded(&v) => v.checked_sub(1).ok_or(OutOfBoundsError)?,
             Bound::Unbounded => parent.x_end,
         };
         if x_end > parent.x_end {
@@ -69,7 +69,7 @@ impl Bounds {
         }
         let y_start = match y.start_bound() {
             Bound::Included(&v) => v,
-            Bound::Excluded(&v) => v + 1,
+            Bound::Excluded(&v) => v.checked_add(1).ok_or(OutOfBoundsError)?,
             Bound::Unbounded => 0,
         };
         if y_start < parent.y_start {
@@ -77,8 +77,8 @@ impl Bounds {
         }
         let y_end = match y.end_bound() {
             Bound::Included(&v) => v,
-            Bound::Excluded(&v) => v - 1,
-            Bound::Unbounded => parent.y_end - 1,
+            Bound::Excluded(&v) => v.checked_sub(1).ok_or(OutOfBoundsError)?,
+            Bound::Unbounded => parent.y_end,
         };
         if y_end > parent.y_end {
             return Err(OutOfBoundsError);
@@ -181,6 +181,13 @@ mod test {
         assert_eq!(b(0u16, 0u16, 0u16, 0u16).set_width(2u16), &b(0, 1, 0, 0));
     }
     #[test]
+    fn new_within_empty_at_zero() {
+        let parent = b(0, 9, 0, 9);
+        assert_eq!(Bounds::new_within(.., ..0, &parent), Err(OutOfBoundsError));
+        assert_eq!(Bounds::new_within(0..0, .., &parent), Err(OutOfBoundsError));
+        assert_eq!(Bounds::new_within(..5, 1..=2, &parent), Ok(b(0, 4, 1, 2)));
+    }
+    #[test]
     fn append() {
         assert_eq!(b(0u16, 0u16, 0u16, 0u16).append(5u16, 5u16), &b(0, 5, 0, 5));
         assert_eq!(b(0u16, 1u16, 0u16, 1u16).append(-1i16, -1i16), &b(0, 0, 0, 0));
@@ -194,6 +201,13 @@ mod test {
         assert_eq!(b(0u16, 1u16, 0u16, 1u16).height(), 2);
     }
     #[test]
+    fn new_within() {
+        let parent = b(0, 799, 0, 479);
+        assert_eq!(Bounds::new_within(.., .., &parent), Ok(parent));
+        assert_eq!(Bounds::new_within(10..20, 5..=5, &parent), Ok(b(10, 19, 5, 5)));
+        assert_eq!(Bounds::new_within(.., ..=480, &parent), Err(OutOfBoundsError));
+    }
+    #[test]
     fn intersect() {
         assert_eq!(b(0u16, 0u16, 0u16, 0u16).intersect(&b(1, 1, 1, 1)), None);
         assert_eq!(b(0u16, 2u16, 0u16, 2u16).intersect(&b(1, 10, 1, 10)), Some(b(1, 2, 1, 2)));
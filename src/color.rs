@@ -0,0 +1,111 @@
+/// 16-bit color, 5 bits red, 6 bits green and 5 bits blue from the most significant bit
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rgb565(pub u16);
+
+impl Rgb565 {
+    /// Builds the color from 8-bit components, dropping the bits that don't fit
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self((r as u16 >> 3) << 11 | (g as u16 >> 2) << 5 | b as u16 >> 3)
+    }
+    pub const fn r(&self) -> u8 {
+        (self.0 >> 11) as u8 & 0x1F
+    }
+    pub const fn g(&self) -> u8 {
+        (self.0 >> 5) as u8 & 0x3F
+    }
+    pub const fn b(&self) -> u8 {
+        self.0 as u8 & 0x1F
+    }
+}
+
+impl From<u16> for Rgb565 {
+    fn from(raw: u16) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<Rgb565> for u16 {
+    fn from(color: Rgb565) -> Self {
+        color.0
+    }
+}
+
+/// 18-bit color, each component in the low 6 bits
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rgb666 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb666 {
+    /// Builds the color from 8-bit components, dropping the bits that don't fit
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            r: r >> 2,
+            g: g >> 2,
+            b: b >> 2,
+        }
+    }
+}
+
+/// 24-bit color
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rgb888 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb888 {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+/// Widens a `bits` wide component to 8 bits, repeating its top bits so that full intensity stays 0xFF
+const fn widen(value: u8, bits: u32) -> u8 {
+    value << (8 - bits) | value >> (2 * bits - 8)
+}
+
+impl From<Rgb565> for Rgb888 {
+    fn from(color: Rgb565) -> Self {
+        Self::new(widen(color.r(), 5), widen(color.g(), 6), widen(color.b(), 5))
+    }
+}
+
+impl From<Rgb666> for Rgb888 {
+    fn from(color: Rgb666) -> Self {
+        Self::new(widen(color.r, 6), widen(color.g, 6), widen(color.b, 6))
+    }
+}
+
+impl From<Rgb888> for Rgb565 {
+    fn from(color: Rgb888) -> Self {
+        Self::new(color.r, color.g, color.b)
+    }
+}
+
+impl From<Rgb888> for Rgb666 {
+    fn from(color: Rgb888) -> Self {
+        Self::new(color.r, color.g, color.b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Rgb565::new(0xFF, 0, 0), Rgb565(0xF800));
+        assert_eq!(Rgb565::new(0, 0xFF, 0), Rgb565(0x07E0));
+        assert_eq!(Rgb888::from(Rgb565(0xFFFF)), Rgb888::new(0xFF, 0xFF, 0xFF));
+        assert_eq!(Rgb888::from(Rgb565(0b0000_1000_0100_0100)), Rgb888::new(0b0000_1000, 0b0000_1000, 0b0010_0001));
+        assert_eq!(Rgb888::from(Rgb666 { r: 0x3F, g: 0, b: 1 }), Rgb888::new(0xFF, 0, 0b0000_0100));
+        for raw in [0x0000, 0xFFFF, 0x1234, 0xABCD, 0x8410] {
+            assert_eq!(Rgb565::from(Rgb888::from(Rgb565(raw))), Rgb565(raw));
+        }
+        assert_eq!(Rgb666::from(Rgb888::new(0xFF, 0x80, 0x03)), Rgb666 { r: 0x3F, g: 0x20, b: 0 });
+    }
+}
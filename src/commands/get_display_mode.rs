@@ -0,0 +1,32 @@
+use packed_struct::{
+    derive::PackedStruct,
+    types::{bits::Bits3, Integer},
+};
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering = "msb0")]
+/// Current display mode, as reported by `get_display_mode` (0x0D)
+pub struct DisplayMode {
+    #[packed_field(bits = "0")]
+    pub vertical_scrolling: bool,
+    #[packed_field(bits = "2")]
+    pub inversion: bool,
+    /// Index of the selected gamma curve
+    #[packed_field(bits = "5..=7")]
+    pub gamma_curve: Integer<u8, Bits3>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn display_mode() {
+        let dm = DisplayMode::unpack(&[0b1010_0011]).unwrap();
+        assert!(dm.vertical_scrolling);
+        assert!(dm.inversion);
+        assert_eq!(*dm.gamma_curve, 3);
+        assert_eq!(DisplayMode::unpack(&[0]).unwrap().pack().unwrap(), [0]);
+    }
+}
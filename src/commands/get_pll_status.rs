@@ -0,0 +1,21 @@
+use packed_struct::derive::PackedStruct;
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering = "msb0")]
+/// PLL status, as reported by `get_pll_status` (0xE4)
+pub struct PllStatus {
+    #[packed_field(bits = "5")]
+    pub locked: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn pll_status() {
+        assert!(PllStatus::unpack(&[0b100]).unwrap().locked);
+        assert!(!PllStatus::unpack(&[0b011]).unwrap().locked);
+    }
+}
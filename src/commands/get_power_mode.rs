@@ -0,0 +1,41 @@
+use packed_struct::derive::PackedStruct;
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering = "msb0")]
+/// Current power mode, as reported by `get_power_mode` (0x0A)
+pub struct PowerMode {
+    #[packed_field(bits = "1")]
+    pub idle_mode: bool,
+    #[packed_field(bits = "2")]
+    pub partial_mode: bool,
+    /// Cleared while the controller is in sleep mode
+    #[packed_field(bits = "3")]
+    pub sleep_out: bool,
+    #[packed_field(bits = "4")]
+    pub normal_mode: bool,
+    #[packed_field(bits = "5")]
+    pub display_on: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn power_mode() {
+        let pm = PowerMode::unpack(&[0b0001_1100]).unwrap();
+        assert_eq!(
+            pm,
+            PowerMode {
+                idle_mode: false,
+                partial_mode: false,
+                sleep_out: true,
+                normal_mode: true,
+                display_on: true,
+            }
+        );
+        let pm = PowerMode::unpack(&[0b0110_0000]).unwrap();
+        assert!(pm.idle_mode && pm.partial_mode && !pm.sleep_out);
+    }
+}
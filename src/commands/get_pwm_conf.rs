@@ -0,0 +1,45 @@
+use super::{set_pwm_conf::BrightnessPrescaler, ControlledBy};
+use packed_struct::derive::PackedStruct;
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering = "msb0")]
+/// PWM configuration as reported by `get_pwm_conf` (0xBF),
+/// the fields of `SetPwmConf` followed by the DBC status
+pub struct PwmStatus {
+    pub pwm_frequency: u8,
+    pub pwm_duty_cycle: u8,
+    #[packed_field(bits = "20", ty = "enum")]
+    pub pwm_control: ControlledBy,
+    #[packed_field(bits = "23")]
+    pub pwm_enable: bool,
+    pub dbc_manual_brightness: u8,
+    pub dbc_minimum_brightness: u8,
+    #[packed_field(bits = "44..=47", ty = "enum")]
+    pub brightness_prescaler: BrightnessPrescaler,
+    #[packed_field(bits = "55")]
+    pub dbc_enabled: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn pwm_status() {
+        let ps = PwmStatus::unpack(&[6, 0xF0, 0b1001, 0x80, 0x10, 0b1111, 1]).unwrap();
+        assert_eq!(
+            ps,
+            PwmStatus {
+                pwm_frequency: 6,
+                pwm_duty_cycle: 0xF0,
+                pwm_control: ControlledBy::Lcdc,
+                pwm_enable: true,
+                dbc_manual_brightness: 0x80,
+                dbc_minimum_brightness: 0x10,
+                brightness_prescaler: BrightnessPrescaler::B192,
+                dbc_enabled: true,
+            }
+        );
+    }
+}
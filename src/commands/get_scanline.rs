@@ -0,0 +1,24 @@
+use packed_struct::{
+    derive::PackedStruct,
+    types::{bits::Bits11, Integer},
+};
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering = "msb0", endian = "msb")]
+/// Line the controller is scanning out, as reported by `get_scanline` (0x45)
+pub struct Scanline {
+    #[packed_field(bits = "5..=15")]
+    pub line: Integer<u16, Bits11>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn scanline() {
+        assert_eq!(*Scanline::unpack(&[0, 0]).unwrap().line, 0);
+        assert_eq!(*Scanline::unpack(&[0b1111_1010, 0x0F]).unwrap().line, 0x20F);
+    }
+}
@@ -1,9 +1,22 @@
+//! Commands of the controller, each one a struct packing its parameters
+
 pub trait CommandCode {
     const CODE: u8;
 }
 pub trait CommandData<const N: usize> {
     fn data(&self) -> [u8; N];
 }
+/// Parameters the controller replies with to a get_* command
+pub trait ResponseData<const N: usize>: Sized {
+    /// `None` if the reply doesn't decode, e.g. a field holds a value not valid for its enum
+    fn from_data(data: [u8; N]) -> Option<Self>;
+}
+/// A get_* command, sent without parameters and answered with `Response`
+pub trait Query: CommandCode + CommandData<0> + Default {
+    type Response;
+    /// Assembles the response from parameters returned by `read`, one byte at a time
+    fn read_response<E>(read: impl FnMut() -> Result<u8, E>) -> Result<Option<Self::Response>, E>;
+}
 
 
 macro_rules! impl_command_int {
@@ -20,11 +33,56 @@ macro_rules! impl_command_int {
     }
 }
 
-macro_rules! impl_command {
-    ($path:ident :: $name:ident, $code:literal) => {
-        mod $path;
-        impl_command_int!($path::$name, $code);
+macro_rules! response_len {
+    ($path:ident :: $name:ident) => {
+        core::mem::size_of::<<$path::$name as packed_struct::PackedStruct>::ByteArray>()
+    };
+}
+
+macro_rules! impl_response_int {
+    ($path:ident :: $name:ident) => {
+        impl ResponseData<{ response_len!($path::$name) }> for $path::$name {
+            fn from_data(data: [u8; { response_len!($path::$name) }]) -> Option<Self> {
+                <$path::$name as packed_struct::PackedStruct>::unpack(&data).ok()
+            }
+        }
+    };
+}
+
+macro_rules! impl_response {
+    (pub $path:ident :: $name:ident) => {
+        pub mod $path;
+        pub use $path::$name;
+        impl_response_int!($path::$name);
+    };
+}
+
+macro_rules! impl_query {
+    ($name:ident, $code:literal, $path:ident :: $response:ident) => {
+        #[derive(Default)]
+        pub struct $name;
+        impl CommandCode for $name {
+            const CODE: u8 = $code;
+        }
+        impl CommandData<0> for $name {
+            fn data(&self) -> [u8; 0] {
+                [0; 0]
+            }
+        }
+        impl Query for $name {
+            type Response = $path::$response;
+            fn read_response<E>(mut read: impl FnMut() -> Result<u8, E>) -> Result<Option<Self::Response>, E> {
+                let mut data = [0; { response_len!($path::$response) }];
+                for byte in data.iter_mut() {
+                    *byte = read()?;
+                }
+                Ok(ResponseData::from_data(data))
+            }
+        }
     };
+}
+
+macro_rules! impl_command {
     (pub $path:ident :: $name:ident, $code:literal) => {
         pub mod $path;
         impl_command_int!($path::$name, $code);
@@ -32,21 +90,75 @@ macro_rules! impl_command {
 }
 
 impl_command!(pub set_pll::SetPll, 0xE0);
-impl_command!(set_pll_mn::SetPllMn, 0xE2);
-impl_command!(set_lshift_freq::SetLShiftFreq, 0xE6);
+impl_command!(pub set_pll_mn::SetPllMn, 0xE2);
+impl_command!(pub set_lshift_freq::SetLShiftFreq, 0xE6);
 impl_command!(pub set_lcd_mode::SetLcdMode, 0xB0);
-impl_command!(set_hori_period::SetHoriPeriod, 0xB4);
-impl_command!(set_vert_period::SetVertPeriod, 0xB6);
-impl_command!(set_gpio_value::SetGpioValue, 0xBA);
-impl_command!(set_gpio_conf::SetGpioConf, 0xB8);
+impl_command!(pub set_hori_period::SetHoriPeriod, 0xB4);
+impl_command!(pub set_vert_period::SetVertPeriod, 0xB6);
+impl_command!(pub set_gpio_value::SetGpioValue, 0xBA);
+impl_command!(pub set_gpio_conf::SetGpioConf, 0xB8);
 impl_command!(pub set_address_mode::SetAddressMode, 0x36);
 impl_command!(pub set_pixel_data_interface::SetPixelDataInterface, 0xF0);
-impl_command!(set_pwm_conf::SetPwmConf, 0xBE);
-impl_command!(set_dbc_conf::SetDbcConf, 0xD0);
-impl_command!(set_column_address::SetColumnAddress, 0x2A);
-impl_command!(set_page_address::SetPageAddress, 0x2B);
-impl_command!(set_scroll_area::SetScrollArea, 0x33);
-impl_command!(set_scroll_start::SetScrollStart, 0x37);
+impl_command!(pub set_pwm_conf::SetPwmConf, 0xBE);
+impl_command!(pub set_dbc_conf::SetDbcConf, 0xD0);
+impl_command!(pub set_column_address::SetColumnAddress, 0x2A);
+impl_command!(pub set_page_address::SetPageAddress, 0x2B);
+impl_command!(pub set_scroll_area::SetScrollArea, 0x33);
+impl_command!(pub set_scroll_start::SetScrollStart, 0x37);
+impl_command!(pub set_dbc_th::SetDbcTh, 0xD4);
+impl_command!(pub set_post_proc::SetPostProc, 0xBC);
+impl_command!(pub set_tear_on::SetTearOn, 0x35);
+impl_command!(pub set_tear_scanline::SetTearScanline, 0x44);
+impl_command!(pub set_partial_area::SetPartialArea, 0x30);
+impl_command!(pub set_gamma_curve::SetGammaCurve, 0x26);
+
+pub mod set_gpiox_rop;
+pub use set_gpiox_rop::GpioRop;
+impl_command_int!(set_gpiox_rop::SetGpio0Rop, 0xC8);
+impl_command_int!(set_gpiox_rop::SetGpio1Rop, 0xCA);
+impl_command_int!(set_gpiox_rop::SetGpio2Rop, 0xCC);
+impl_command_int!(set_gpiox_rop::SetGpio3Rop, 0xCE);
+
+pub mod set_lcd_genx;
+pub use set_lcd_genx::LcdGen;
+impl_command_int!(set_lcd_genx::SetLcdGen0, 0xC0);
+impl_command_int!(set_lcd_genx::SetLcdGen1, 0xC2);
+impl_command_int!(set_lcd_genx::SetLcdGen2, 0xC4);
+impl_command_int!(set_lcd_genx::SetLcdGen3, 0xC6);
+
+impl_response!(pub get_power_mode::PowerMode);
+impl_response!(pub get_display_mode::DisplayMode);
+impl_response!(pub get_pwm_conf::PwmStatus);
+impl_response!(pub get_pll_status::PllStatus);
+impl_response!(pub get_scanline::Scanline);
+impl_response_int!(set_address_mode::SetAddressMode);
+impl_response_int!(set_lcd_mode::SetLcdMode);
+impl_response_int!(set_hori_period::SetHoriPeriod);
+impl_response_int!(set_vert_period::SetVertPeriod);
+impl_response_int!(set_gpio_conf::SetGpioConf);
+impl_response_int!(set_gpio_value::SetGpioValue);
+impl_response_int!(set_dbc_conf::SetDbcConf);
+impl_response_int!(set_pll_mn::SetPllMn);
+impl_response_int!(set_lshift_freq::SetLShiftFreq);
+impl_response_int!(set_pixel_data_interface::SetPixelDataInterface);
+impl_response_int!(set_post_proc::SetPostProc);
+
+impl_query!(GetPowerMode, 0x0A, get_power_mode::PowerMode);
+impl_query!(GetAddressMode, 0x0B, set_address_mode::SetAddressMode);
+impl_query!(GetDisplayMode, 0x0D, get_display_mode::DisplayMode);
+impl_query!(GetScanline, 0x45, get_scanline::Scanline);
+impl_query!(GetLcdMode, 0xB1, set_lcd_mode::SetLcdMode);
+impl_query!(GetHoriPeriod, 0xB5, set_hori_period::SetHoriPeriod);
+impl_query!(GetVertPeriod, 0xB7, set_vert_period::SetVertPeriod);
+impl_query!(GetGpioConf, 0xB9, set_gpio_conf::SetGpioConf);
+impl_query!(GetGpioStatus, 0xBB, set_gpio_value::SetGpioValue);
+impl_query!(GetPostProc, 0xBD, set_post_proc::SetPostProc);
+impl_query!(GetPwmConf, 0xBF, get_pwm_conf::PwmStatus);
+impl_query!(GetDbcConf, 0xD1, set_dbc_conf::SetDbcConf);
+impl_query!(GetPllMn, 0xE3, set_pll_mn::SetPllMn);
+impl_query!(GetPllStatus, 0xE4, get_pll_status::PllStatus);
+impl_query!(GetLShiftFreq, 0xE7, set_lshift_freq::SetLShiftFreq);
+impl_query!(GetPixelDataInterface, 0xF1, set_pixel_data_interface::SetPixelDataInterface);
 
 use packed_struct::derive::PrimitiveEnum_u8;
 #[derive(PrimitiveEnum_u8, Clone, Copy, Debug, PartialEq)]
@@ -73,7 +185,7 @@ simple_write!(SoftReset, 0x01);
 simple_write!(EnterSleepMode, 0x10);
 simple_write!(ExitSleepMode, 0x11);
 simple_write!(EnterPartialMode, 0x12);
-simple_write!(EnterNormalMode, 0x12);
+simple_write!(EnterNormalMode, 0x13);
 simple_write!(ExitInvertMode, 0x20);
 simple_write!(EnterInvertMode, 0x21);
 simple_write!(SetDisplayOff, 0x28);
@@ -81,50 +193,12 @@ simple_write!(SetDisplayOn, 0x29);
 simple_write!(WriteMemoryStart, 0x2C);
 simple_write!(ReadMemoryStart, 0x2E);
 simple_write!(SetTearOff, 0x34);
-simple_write!(ExistIdleMode, 0x38);
+simple_write!(ExitIdleMode, 0x38);
 simple_write!(EnterIdleMode, 0x39);
 simple_write!(WriteMemoryContinue, 0x3C);
 simple_write!(ReadMemoryContinue, 0x3E);
 simple_write!(SetDeepSleep, 0xE5);
 
-// fn as_u8(primitive_enum: isize) -> u8 {
-//     use core::convert::TryInto;
-//     TryInto::<usize>::try_into(primitive_enum).unwrap().try_into().unwrap()
-// }
-
-// macro_rules! simpl_write_primitive_enum {
-//     ($name:tt, $code:literal) => {
-//         impl crate::Command for $name {
-//             fn send<I>(&self, ifc: &mut I) -> Result<(), I::Error>
-//             where
-//                 I: crate::WriteOnlyInterface,
-//             {
-//                 let code: u8 = $code;
-//                 ifc.write_command(code)?;
-//                 ifc.write_data(&[as_u8(*self as isize)])
-//             }
-//         }
-//     };
-// }
-
-// #[derive(Clone, Copy)]
-// pub enum SetGammaCurve {
-//     Curve0 = 0b0001,
-//     Curve1 = 0b0010,
-//     Curve2 = 0b0100,
-//     Curve3 = 0b1000,
-// }
-// simpl_write_primitive_enum!(SetGammaCurve, 0x26);
-
-// #[derive(Clone, Copy)]
-// pub enum SetTearOn {
-//     /// The tearing effect output line consists of V-blanking information only
-//     V = 0,
-//     /// The tearing effect output line consists of both V-blanking and H-blanking information by set_tear_scanline(0x44)
-//     VH = 1,
-// }
-// simpl_write_primitive_enum!(SetTearOn, 0x35);
-
 // pub struct SetScrollStart(u16);
 // impl WriteCommand for SetScrollStart {
 //     fn send<I>(&self, mut ifc: I) -> Result<(), DisplayError>
@@ -135,14 +209,3 @@ simple_write!(SetDeepSleep, 0xE5);
 //         ifc.send_data(DataFormat::U8(&self.0.to_be_bytes()))
 //     }
 // }
-
-// pub struct SetTearScanline(u16);
-// impl WriteCommand for SetTearScanline {
-//     fn send<I>(&self, mut ifc: I) -> Result<(), DisplayError>
-//     where
-//         I: WriteOnlyDataCommand,
-//     {
-//         ifc.send_commands(DataFormat::U8(&[0x44]))?;
-//         ifc.send_data(DataFormat::U8(&self.0.to_be_bytes()))
-//     }
-// }
@@ -0,0 +1,52 @@
+use packed_struct::derive::{PackedStruct, PrimitiveEnum_u8};
+
+#[derive(PrimitiveEnum_u8, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GammaCurve {
+    #[default]
+    Curve0 = 0b0001,
+    Curve1 = 0b0010,
+    Curve2 = 0b0100,
+    Curve3 = 0b1000,
+}
+
+impl GammaCurve {
+    /// Curve with index `index`, as reported by `get_display_mode` (0x0D)
+    pub const fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0 => Some(GammaCurve::Curve0),
+            1 => Some(GammaCurve::Curve1),
+            2 => Some(GammaCurve::Curve2),
+            3 => Some(GammaCurve::Curve3),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering = "msb0")]
+/// Select the gamma curve used by the display
+pub struct SetGammaCurve {
+    #[packed_field(bits = "4..=7", ty = "enum")]
+    pub curve: GammaCurve,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn set_gamma_curve() {
+        let mut gc = SetGammaCurve { curve: GammaCurve::Curve0 };
+        assert_eq!(gc.pack().unwrap(), [0b0001]);
+        gc.curve = GammaCurve::Curve3;
+        assert_eq!(gc.pack().unwrap(), [0b1000]);
+    }
+
+    #[test]
+    fn from_index() {
+        assert_eq!(GammaCurve::from_index(0), Some(GammaCurve::Curve0));
+        assert_eq!(GammaCurve::from_index(2), Some(GammaCurve::Curve2));
+        assert_eq!(GammaCurve::from_index(4), None);
+    }
+}
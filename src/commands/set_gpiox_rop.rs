@@ -1,8 +1,5 @@
-use super::{DataFormat, DisplayError, WriteCommand, WriteOnlyDataCommand};
-use packed_struct::{
-    derive::{PackedStruct, PrimitiveEnum_u8},
-    PackedStruct,
-};
+use super::{CommandCode, CommandData};
+use packed_struct::derive::{PackedStruct, PrimitiveEnum_u8};
 
 #[derive(PrimitiveEnum_u8, Clone, Copy, Debug, PartialEq)]
 pub enum Source {
@@ -12,8 +9,11 @@ pub enum Source {
     Generator3 = 0b11,
 }
 
+/// ROP configuration of one of the GPIOs, `set_gpio0_rop` (0xC8) to `set_gpio3_rop` (0xCE)
+pub trait GpioRop: CommandCode + CommandData<2> {}
+
 macro_rules! gpio_rop {
-    ($struct_name:ident, $camel_case:ident, $code:literal) => {
+    ($struct_name:ident, $camel_case:ident) => {
         #[derive(PackedStruct, Debug, PartialEq)]
         #[packed_struct(bit_numbering = "msb0")]
         pub struct $struct_name {
@@ -35,19 +35,12 @@ macro_rules! gpio_rop {
             pub rop_operation_mux: u8,
         }
 
-        impl WriteCommand for $struct_name {
-            fn send<I>(&self, mut ifc: I) -> Result<(), DisplayError>
-            where
-                I: WriteOnlyDataCommand,
-            {
-                ifc.send_commands(DataFormat::U8(&[$code]))?;
-                ifc.send_data(DataFormat::U8(&self.pack().unwrap()))
-            }
-        }
+        impl GpioRop for $struct_name {}
 
         #[cfg(test)]
         mod $camel_case {
             use super::*;
+            use packed_struct::PackedStruct;
 
             #[test]
             fn $camel_case() {
@@ -66,13 +59,12 @@ macro_rules! gpio_rop {
                 assert_eq!(gc.pack().unwrap(), [0b01100110, 0]);
                 gc.rop_operation_mux = 255;
                 assert_eq!(gc.pack().unwrap(), [0b01100110, 255]);
-
             }
         }
     }
 }
 
-gpio_rop!(SetGpio0Rop, set_gpio0_rop, 0xC8);
-gpio_rop!(SetGpio1Rop, set_gpio1_rop, 0xCA);
-gpio_rop!(SetGpio2Rop, set_gpio2_rop, 0xCC);
-gpio_rop!(SetGpio3Rop, set_gpio3_rop, 0xCD);
+gpio_rop!(SetGpio0Rop, set_gpio0_rop);
+gpio_rop!(SetGpio1Rop, set_gpio1_rop);
+gpio_rop!(SetGpio2Rop, set_gpio2_rop);
+gpio_rop!(SetGpio3Rop, set_gpio3_rop);
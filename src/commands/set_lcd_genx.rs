@@ -1,8 +1,7 @@
-use super::{DataFormat, DisplayError, WriteCommand, WriteOnlyDataCommand};
+use super::{CommandCode, CommandData};
 use packed_struct::{
     derive::{PackedStruct, PrimitiveEnum_u8},
     types::{bits::Bits11, Integer},
-    PackedStruct,
 };
 
 #[derive(PrimitiveEnum_u8, Clone, Copy, Debug, PartialEq)]
@@ -23,8 +22,11 @@ pub enum ToggleMode {
     ToggleByFrame = 0b011,
 }
 
+/// Configuration of one of the LCD signal generators, `set_lcd_gen0` (0xC0) to `set_lcd_gen3` (0xC6)
+pub trait LcdGen: CommandCode + CommandData<7> {}
+
 macro_rules! LcdGen {
-    ($struct_name:ident, $camel_case:ident, $code:literal) => {
+    ($struct_name:ident, $camel_case:ident) => {
         #[derive(PackedStruct, Debug, PartialEq)]
         #[packed_struct(bit_numbering = "msb0", endian = "msb")]
         pub struct $struct_name {
@@ -44,19 +46,12 @@ macro_rules! LcdGen {
             pub period: Integer<u16, Bits11>,
         }
 
-        impl WriteCommand for $struct_name {
-            fn send<I>(&self, mut ifc: I) -> Result<(), DisplayError>
-            where
-                I: WriteOnlyDataCommand,
-            {
-                ifc.send_commands(DataFormat::U8(&[$code]))?;
-                ifc.send_data(DataFormat::U8(&self.pack().unwrap()))
-            }
-        }
+        impl LcdGen for $struct_name {}
 
         #[cfg(test)]
         mod $camel_case {
             use super::*;
+            use packed_struct::PackedStruct;
 
             #[test]
             fn $camel_case() {
@@ -101,7 +96,7 @@ macro_rules! LcdGen {
     };
 }
 
-LcdGen!(SetLcdGen0, set_lcd_gen0, 0xC0);
-LcdGen!(SetLcdGen1, set_lcd_gen1, 0xC2);
-LcdGen!(SetLcdGen2, set_lcd_gen2, 0xC4);
-LcdGen!(SetLcdGen3, set_lcd_gen3, 0xC6);
+LcdGen!(SetLcdGen0, set_lcd_gen0);
+LcdGen!(SetLcdGen1, set_lcd_gen1);
+LcdGen!(SetLcdGen2, set_lcd_gen2);
+LcdGen!(SetLcdGen3, set_lcd_gen3);
@@ -0,0 +1,25 @@
+use packed_struct::derive::PackedStruct;
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering = "msb0", endian = "msb")]
+/// Frame memory rows shown in partial display mode, `start_row` to `end_row` inclusive
+pub struct SetPartialArea {
+    pub start_row: u16,
+    pub end_row: u16,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn set_partial_area() {
+        let mut pa = SetPartialArea { start_row: 0, end_row: 0 };
+        assert_eq!(pa.pack().unwrap(), [0, 0, 0, 0]);
+        pa.start_row = 0b00000001_00000010;
+        assert_eq!(pa.pack().unwrap(), [1, 2, 0, 0]);
+        pa.end_row = 0b00000001_11011111;
+        assert_eq!(pa.pack().unwrap(), [1, 2, 1, 0b11011111]);
+    }
+}
@@ -1,38 +1,34 @@
-use super::{DataFormat, DisplayError, WriteCommand, WriteOnlyDataCommand};
-use packed_struct::{
-    derive::{PackedStruct, PrimitiveEnum_u8},
-    PackedStruct,
-};
-
-#[derive(PrimitiveEnum_u8, Clone, Copy, Debug, PartialEq)]
-pub enum Output {
-    Zero = 0,
-    One = 1,
-}
+use packed_struct::derive::PackedStruct;
 
 #[derive(PackedStruct, Debug, PartialEq)]
 #[packed_struct(bit_numbering = "msb0")]
+/// Set the image post processor, also the reply of `get_post_proc` (0xBD)
 pub struct SetPostProc {
+    /// Contrast value, 0x40 leaves the image unchanged
     pub contrast: u8,
+    /// Brightness value, 0x80 leaves the image unchanged
     pub brightness: u8,
+    /// Saturation value, 0x40 leaves the image unchanged
     pub saturation: u8,
+    /// Enable of the post processor
     #[packed_field(bits = "31")]
     pub enable: bool,
 }
 
-impl WriteCommand for SetPostProc {
-    fn send<I>(&self, mut ifc: I) -> Result<(), DisplayError>
-    where
-        I: WriteOnlyDataCommand,
-    {
-        ifc.send_commands(DataFormat::U8(&[0xBC]))?;
-        ifc.send_data(DataFormat::U8(&self.pack().unwrap()))
-    }
+impl SetPostProc {
+    /// Values that leave the image unchanged
+    pub const NEUTRAL: Self = Self {
+        contrast: 0x40,
+        brightness: 0x80,
+        saturation: 0x40,
+        enable: false,
+    };
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use packed_struct::PackedStruct;
 
     #[test]
     fn set_post_proc() {
@@ -51,5 +47,6 @@ mod test {
         assert_eq!(pp.pack().unwrap(), [0b11000111, 0b11000111, 0b11000111, 0]);
         pp.enable = true;
         assert_eq!(pp.pack().unwrap(), [0b11000111, 0b11000111, 0b11000111, 0b1]);
+        assert_eq!(SetPostProc::unpack(&[0x40, 0x80, 0x40, 0]).unwrap(), SetPostProc::NEUTRAL);
     }
 }
@@ -0,0 +1,31 @@
+use packed_struct::derive::{PackedStruct, PrimitiveEnum_u8};
+
+#[derive(PrimitiveEnum_u8, Clone, Copy, Debug, PartialEq)]
+pub enum TearingMode {
+    /// The tearing effect output line consists of V-blanking information only
+    VBlank = 0,
+    /// The tearing effect output line consists of both V-blanking and H-blanking information by set_tear_scanline (0x44)
+    VHBlank = 1,
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering = "msb0")]
+/// Turn on the tearing effect output signal (TE)
+pub struct SetTearOn {
+    #[packed_field(bits = "7", ty = "enum")]
+    pub mode: TearingMode,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn set_tear_on() {
+        let mut to = SetTearOn { mode: TearingMode::VBlank };
+        assert_eq!(to.pack().unwrap(), [0]);
+        to.mode = TearingMode::VHBlank;
+        assert_eq!(to.pack().unwrap(), [1]);
+    }
+}
@@ -0,0 +1,22 @@
+use packed_struct::derive::PackedStruct;
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering = "msb0", endian = "msb")]
+/// Turn on the tearing effect output signal when the display reaches line `scanline`
+pub struct SetTearScanline {
+    pub scanline: u16,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn set_tear_scanline() {
+        let mut ts = SetTearScanline { scanline: 0 };
+        assert_eq!(ts.pack().unwrap(), [0, 0]);
+        ts.scanline = 0b00000111_00000001;
+        assert_eq!(ts.pack().unwrap(), [0b111, 1]);
+    }
+}
@@ -1,7 +1,10 @@
 use core::ops::RangeBounds;
 
 pub trait Display {
+    /// Native width of the panel, drawing follows the current orientation which may swap it with `HEIGHT`,
+    /// see `Ssd1963::width`
     const WIDTH: u16;
+    /// Native height of the panel, see `WIDTH`
     const HEIGHT: u16;
     type Color;
     type Error;
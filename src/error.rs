@@ -0,0 +1,47 @@
+use crate::panel::TimingError;
+use crate::pll::ClockError;
+use crate::scroll::ScrollError;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error<E> {
+    /// The bus interface failed
+    Interface(E),
+    /// The requested clocks can't be generated
+    Clock(ClockError),
+    /// The panel timing is inconsistent
+    Timing(TimingError),
+    /// `get_pll_status` didn't report a locked PLL within `Clocks::lock_timeout_us`
+    PllLockTimeout,
+    /// The controller's reply to a query doesn't decode
+    InvalidResponse,
+    /// The scroll region or position doesn't fit the screen
+    Scroll(ScrollError),
+    /// Two frames of the screen don't fit the controller's frame buffer
+    FrameBufferTooSmall,
+    /// Double buffering works only with the panel in its native rotation
+    UnsupportedOrientation,
+    /// The frame buffer can't be accessed while the controller sleeps
+    Asleep,
+    /// The partial display rows are empty or don't fit the screen
+    InvalidPartialRegion,
+    /// No vertical blanking started within two frames
+    VblankTimeout,
+}
+
+impl<E> From<ClockError> for Error<E> {
+    fn from(err: ClockError) -> Self {
+        Error::Clock(err)
+    }
+}
+
+impl<E> From<TimingError> for Error<E> {
+    fn from(err: TimingError) -> Self {
+        Error::Timing(err)
+    }
+}
+
+impl<E> From<ScrollError> for Error<E> {
+    fn from(err: ScrollError) -> Self {
+        Error::Scroll(err)
+    }
+}
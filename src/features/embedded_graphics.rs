@@ -0,0 +1,182 @@
+use crate::{color, Bounds, Error, PixelFormat, PortWord, Screen, Ssd1963, WriteOnlyInterface};
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Size},
+    pixelcolor::{self, IntoStorage, PixelColor, RgbColor},
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
+
+/// Colors of this crate that have an embedded-graphics counterpart
+pub trait GraphicsColor: Sized {
+    type Color: PixelColor;
+    fn from_graphics(color: Self::Color) -> Self;
+}
+
+impl GraphicsColor for color::Rgb565 {
+    type Color = pixelcolor::Rgb565;
+    fn from_graphics(color: pixelcolor::Rgb565) -> Self {
+        Self(color.into_storage())
+    }
+}
+
+impl GraphicsColor for color::Rgb666 {
+    type Color = pixelcolor::Rgb666;
+    fn from_graphics(color: pixelcolor::Rgb666) -> Self {
+        Self {
+            r: color.r(),
+            g: color.g(),
+            b: color.b(),
+        }
+    }
+}
+
+impl GraphicsColor for color::Rgb888 {
+    type Color = pixelcolor::Rgb888;
+    fn from_graphics(color: pixelcolor::Rgb888) -> Self {
+        Self::new(color.r(), color.g(), color.b())
+    }
+}
+
+/// Part of `area` that's on the screen, `None` if nothing is
+fn visible<Lcd, Delay, Interface, Format>(display: &Ssd1963<Lcd, Delay, Interface, Format>, area: &Rectangle) -> Option<Bounds>
+where
+    Lcd: Screen,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    Interface: WriteOnlyInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
+{
+    let area = area.intersection(&display.bounding_box());
+    let bottom_right = area.bottom_right()?;
+    Some(Bounds {
+        x_start: area.top_left.x as u16,
+        x_end: bottom_right.x as u16,
+        y_start: area.top_left.y as u16,
+        y_end: bottom_right.y as u16,
+    })
+}
+
+impl<Lcd, Delay, Interface, Format> OriginDimensions for Ssd1963<Lcd, Delay, Interface, Format>
+where
+    Lcd: Screen,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    Interface: WriteOnlyInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
+{
+    fn size(&self) -> Size {
+        Size::new(self.width().into(), self.height().into())
+    }
+}
+
+impl<Lcd, Delay, Interface, Format> DrawTarget for Ssd1963<Lcd, Delay, Interface, Format>
+where
+    Lcd: Screen,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    Interface: WriteOnlyInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
+    Format::Color: GraphicsColor,
+{
+    type Color = <Format::Color as GraphicsColor>::Color;
+    type Error = Error<Interface::Error>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.check_awake()?;
+        let screen = self.bounding_box();
+        for Pixel(point, color) in pixels {
+            if screen.contains(point) {
+                let (x, y) = (point.x as u16, point.y as u16);
+                self.write_area_color(x..=x, y..=y, GraphicsColor::from_graphics(color))
+                    .map_err(Error::Interface)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.check_awake()?;
+        let bounds = match visible(self, area) {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        let screen = self.bounding_box();
+        // the colors run row by row over the whole area, the visible ones keep that order
+        let mut colors = area
+            .points()
+            .zip(colors)
+            .filter(|(point, _)| screen.contains(*point))
+            .map(|(_, color)| GraphicsColor::from_graphics(color));
+        self.fill_area_bounds(&bounds, &mut colors).map_err(Error::Interface)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        match visible(self, area) {
+            Some(bounds) => self.fill_area_color(
+                bounds.x_start..=bounds.x_end,
+                bounds.y_start..=bounds.y_end,
+                GraphicsColor::from_graphics(color),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear_screen(GraphicsColor::from_graphics(color))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commands::{CommandCode, SetColumnAddress, SetPageAddress, WriteMemoryStart};
+    use crate::mock::{self, Bus};
+    use embedded_graphics_core::geometry::Point;
+
+    fn rectangle(x: i32, y: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    fn bounds(x_start: u16, x_end: u16, y_start: u16, y_end: u16) -> Bounds {
+        Bounds {
+            x_start,
+            x_end,
+            y_start,
+            y_end,
+        }
+    }
+
+    #[test]
+    fn visible_clips_to_screen() {
+        let driver = mock::driver(&Bus::default());
+        assert_eq!(visible(&driver, &rectangle(10, 20, 30, 40)), Some(bounds(10, 39, 20, 59)));
+        assert_eq!(visible(&driver, &rectangle(-5, -5, 10, 10)), Some(bounds(0, 4, 0, 4)));
+        assert_eq!(visible(&driver, &rectangle(470, 268, 20, 10)), Some(bounds(470, 479, 268, 271)));
+        assert_eq!(visible(&driver, &rectangle(-20, 0, 10, 10)), None);
+        assert_eq!(visible(&driver, &rectangle(0, 272, 10, 10)), None);
+        assert_eq!(visible(&driver, &rectangle(10, 10, 0, 0)), None);
+    }
+
+    #[test]
+    fn fill_contiguous_skips_hidden_colors() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        let colors = (0..8).map(|i| pixelcolor::Rgb565::new(i, 0, 0));
+        driver.fill_contiguous(&rectangle(478, -1, 4, 2), colors).unwrap();
+        assert_eq!(
+            bus.commands(),
+            [
+                (SetColumnAddress::CODE, vec![0x01, 0xDE, 0x01, 0xDF]),
+                (SetPageAddress::CODE, vec![0, 0, 0, 0]),
+                (WriteMemoryStart::CODE, vec![4 << 11, 5 << 11]),
+            ]
+        );
+        bus.clear();
+        driver.fill_contiguous(&rectangle(480, 0, 4, 2), (0..8).map(|i| pixelcolor::Rgb565::new(i, 0, 0))).unwrap();
+        assert_eq!(bus.commands(), []);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::gpio16bit_interface::{ReadWritePort, WritePort};
+use crate::interface::{ReadWritePort, WritePort};
 use stm32f1xx_hal::gpio::{
     gpioa::{CRH as ACRH, CRL as ACRL, PA0, PA1, PA10, PA11, PA12, PA13, PA14, PA15, PA2, PA3, PA4, PA5, PA6, PA7, PA8, PA9},
     gpiob::{CRH as BCRH, CRL as BCRL, PB0, PB1, PB10, PB11, PB12, PB13, PB14, PB15, PB2, PB3, PB4, PB5, PB6, PB7, PB8, PB9},
@@ -68,6 +68,7 @@ impl PortA {
     }
 }
 impl WritePort for PortA {
+    type Word = u16;
     fn set_value(&mut self, value: u16) {
         unsafe { (&*stm32f1xx_hal::pac::GPIOA::ptr()).odr.write(|w| w.bits(value as u32)) };
     }
@@ -104,6 +105,7 @@ impl RwPortA {
     }
 }
 impl WritePort for RwPortA {
+    type Word = u16;
     fn set_value(&mut self, value: u16) {
         unsafe { (&*stm32f1xx_hal::pac::GPIOA::ptr()).odr.write(|w| w.bits(value.into())) };
     }
@@ -187,6 +189,7 @@ impl PortB {
     }
 }
 impl WritePort for PortB {
+    type Word = u16;
     fn set_value(&mut self, value: u16) {
         unsafe { (&*stm32f1xx_hal::pac::GPIOB::ptr()).odr.write(|w| w.bits(value as u32)) };
     }
@@ -223,6 +226,7 @@ impl RwPortB {
     }
 }
 impl WritePort for RwPortB {
+    type Word = u16;
     fn set_value(&mut self, value: u16) {
         unsafe { (&*stm32f1xx_hal::pac::GPIOB::ptr()).odr.write(|w| w.bits(value.into())) };
     }
@@ -304,6 +308,7 @@ impl PortC {
     }
 }
 impl WritePort for PortC {
+    type Word = u16;
     fn set_value(&mut self, value: u16) {
         unsafe { (&*stm32f1xx_hal::pac::GPIOC::ptr()).odr.write(|w| w.bits(value as u32)) };
     }
@@ -340,6 +345,7 @@ impl RwPortC {
     }
 }
 impl WritePort for RwPortC {
+    type Word = u16;
     fn set_value(&mut self, value: u16) {
         unsafe { (&*stm32f1xx_hal::pac::GPIOC::ptr()).odr.write(|w| w.bits(value.into())) };
     }
@@ -0,0 +1,251 @@
+use crate::commands::set_gpio_value::Output;
+use crate::commands::{SetGpioConf, SetGpioValue};
+use crate::interface::ReadWriteInterface;
+use crate::{Error, PixelFormat, PortWord, Screen, Ssd1963, WriteOnlyInterface};
+use core::cell::RefCell;
+pub use crate::commands::set_gpio_conf::{Direction, PowerControl};
+pub use crate::commands::ControlledBy;
+
+/// One of the controller's general purpose pins
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gpio {
+    Gpio0 = 0,
+    Gpio1 = 1,
+    Gpio2 = 2,
+    Gpio3 = 3,
+}
+
+impl Gpio {
+    const fn mask(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// GPIO configuration and output levels last sent to the controller
+///
+/// Starts with every pin a host controlled input, the reset value of `set_gpio_conf`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct GpioState {
+    lcdc: u8,
+    outputs: u8,
+    power_control: PowerControl,
+    values: u8,
+}
+
+impl Default for GpioState {
+    fn default() -> Self {
+        Self {
+            lcdc: 0,
+            outputs: 0,
+            power_control: PowerControl::Normal,
+            values: 0,
+        }
+    }
+}
+
+const fn bit(mask: u8, pin: Gpio) -> bool {
+    mask & pin.mask() != 0
+}
+
+const fn with_bit(mask: u8, pin: Gpio, set: bool) -> u8 {
+    if set {
+        mask | pin.mask()
+    } else {
+        mask & !pin.mask()
+    }
+}
+
+impl GpioState {
+    pub(crate) fn configure(&mut self, pin: Gpio, control: ControlledBy, direction: Direction) {
+        self.lcdc = with_bit(self.lcdc, pin, control == ControlledBy::Lcdc);
+        self.outputs = with_bit(self.outputs, pin, direction == Direction::Output);
+    }
+
+    pub(crate) fn set_power_control(&mut self, power_control: PowerControl) {
+        self.power_control = power_control;
+    }
+
+    pub(crate) fn set_level(&mut self, pin: Gpio, high: bool) {
+        self.values = with_bit(self.values, pin, high);
+    }
+
+    pub(crate) fn is_set_high(&self, pin: Gpio) -> bool {
+        bit(self.values, pin)
+    }
+
+    pub(crate) fn conf(&self) -> SetGpioConf {
+        let control = |pin| if bit(self.lcdc, pin) { ControlledBy::Lcdc } else { ControlledBy::Host };
+        let direction = |pin| if bit(self.outputs, pin) { Direction::Output } else { Direction::Input };
+        SetGpioConf {
+            gpio3_conf: control(Gpio::Gpio3),
+            gpio2_conf: control(Gpio::Gpio2),
+            gpio1_conf: control(Gpio::Gpio1),
+            gpio0_conf: control(Gpio::Gpio0),
+            gpio3_dir: direction(Gpio::Gpio3),
+            gpio2_dir: direction(Gpio::Gpio2),
+            gpio1_dir: direction(Gpio::Gpio1),
+            gpio0_dir: direction(Gpio::Gpio0),
+            gpio0_power_control: self.power_control,
+        }
+    }
+
+    pub(crate) fn value(&self) -> SetGpioValue {
+        let output = |pin| if bit(self.values, pin) { Output::One } else { Output::Zero };
+        SetGpioValue {
+            gpio3_value: output(Gpio::Gpio3),
+            gpio2_value: output(Gpio::Gpio2),
+            gpio1_value: output(Gpio::Gpio1),
+            gpio0_value: output(Gpio::Gpio0),
+        }
+    }
+}
+
+/// Level of `pin` in a `get_gpio_status` (0xBB) reply
+pub(crate) fn level(status: &SetGpioValue, pin: Gpio) -> bool {
+    let output = match pin {
+        Gpio::Gpio0 => status.gpio0_value,
+        Gpio::Gpio1 => status.gpio1_value,
+        Gpio::Gpio2 => status.gpio2_value,
+        Gpio::Gpio3 => status.gpio3_value,
+    };
+    output == Output::One
+}
+
+/// A controller GPIO as an `embedded_hal` pin, borrowing the driver, see `Ssd1963::gpio`
+///
+/// The pin has to be configured as a host controlled output or input first, see `Ssd1963::configure_gpio`.
+///
+/// It holds the driver's mutable borrow, so there's only one pin at a time and nothing can be drawn while it lives.
+/// Code that keeps a GPIO for longer, e.g. to switch the panel power, should call `Ssd1963::set_gpio` when needed instead.
+pub struct GpioPin<'d, Lcd, Delay, Interface, Format>
+where
+    Lcd: Screen,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    Interface: WriteOnlyInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
+{
+    driver: RefCell<&'d mut Ssd1963<Lcd, Delay, Interface, Format>>,
+    pin: Gpio,
+}
+
+impl<'d, Lcd, Delay, Interface, Format> GpioPin<'d, Lcd, Delay, Interface, Format>
+where
+    Lcd: Screen,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    Interface: WriteOnlyInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
+{
+    pub(crate) fn new(driver: &'d mut Ssd1963<Lcd, Delay, Interface, Format>, pin: Gpio) -> Self {
+        Self {
+            driver: RefCell::new(driver),
+            pin,
+        }
+    }
+}
+
+impl<Lcd, Delay, Interface, Format> embedded_hal::digital::v2::OutputPin for GpioPin<'_, Lcd, Delay, Interface, Format>
+where
+    Lcd: Screen,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    Interface: WriteOnlyInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
+{
+    type Error = Error<Interface::Error>;
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.driver.get_mut().set_gpio(self.pin, false)
+    }
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.driver.get_mut().set_gpio(self.pin, true)
+    }
+}
+
+impl<Lcd, Delay, Interface, Format> embedded_hal::digital::v2::StatefulOutputPin for GpioPin<'_, Lcd, Delay, Interface, Format>
+where
+    Lcd: Screen,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    Interface: WriteOnlyInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
+{
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.driver.borrow().gpio.is_set_high(self.pin))
+    }
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.driver.borrow().gpio.is_set_high(self.pin))
+    }
+}
+
+impl<Lcd, Delay, Interface, Format> embedded_hal::digital::v2::InputPin for GpioPin<'_, Lcd, Delay, Interface, Format>
+where
+    Lcd: Screen,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    Interface: ReadWriteInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
+{
+    type Error = Error<Interface::Error>;
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.driver.borrow_mut().read_gpio(self.pin)
+    }
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{self, Bus};
+    use embedded_hal::digital::v2::{InputPin, OutputPin, StatefulOutputPin};
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn conf() {
+        let mut state = GpioState::default();
+        assert_eq!(state.conf().pack().unwrap(), [0, 1]);
+        state.configure(Gpio::Gpio0, ControlledBy::Host, Direction::Output);
+        state.configure(Gpio::Gpio3, ControlledBy::Lcdc, Direction::Output);
+        state.set_power_control(PowerControl::Power);
+        assert_eq!(state.conf().pack().unwrap(), [0b1000_1001, 0]);
+        state.configure(Gpio::Gpio3, ControlledBy::Host, Direction::Input);
+        assert_eq!(state.conf().pack().unwrap(), [0b0000_0001, 0]);
+    }
+
+    #[test]
+    fn value() {
+        let mut state = GpioState::default();
+        state.set_level(Gpio::Gpio0, true);
+        state.set_level(Gpio::Gpio2, true);
+        assert_eq!(state.value().pack().unwrap(), [0b0101]);
+        assert!(state.is_set_high(Gpio::Gpio2));
+        state.set_level(Gpio::Gpio2, false);
+        assert_eq!(state.value().pack().unwrap(), [0b0001]);
+        let status = SetGpioValue::unpack(&[0b1010]).unwrap();
+        assert!(level(&status, Gpio::Gpio3));
+        assert!(!level(&status, Gpio::Gpio2));
+        assert!(level(&status, Gpio::Gpio1));
+        assert!(!level(&status, Gpio::Gpio0));
+    }
+
+    #[test]
+    fn output_pin() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        driver.set_gpio(Gpio::Gpio0, true).unwrap();
+        bus.clear();
+        let mut pin = driver.gpio(Gpio::Gpio2);
+        pin.set_high().unwrap();
+        assert_eq!(pin.is_set_high(), Ok(true));
+        pin.set_low().unwrap();
+        assert_eq!(pin.is_set_low(), Ok(true));
+        assert_eq!(bus.commands(), [(0xBA, vec![0b0101]), (0xBA, vec![0b0001])]);
+    }
+
+    #[test]
+    fn input_pin() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        bus.queue_reads(&[0b1010, 0b1010]);
+        assert_eq!(driver.gpio(Gpio::Gpio1).is_high(), Ok(true));
+        assert_eq!(driver.gpio(Gpio::Gpio0).is_low(), Ok(true));
+        assert_eq!(bus.codes(), [0xBB, 0xBB]);
+    }
+}
@@ -0,0 +1,343 @@
+use crate::interface::{ChipSelect, NoPin, ReadWriteInterface, ReadWritePort, Reader, WriteOnlyInterface, WritePort, Writer};
+use crate::Clocks;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Drives the wrapped pin to the opposite level
+///
+/// The 6800 E strobe is active high and latches on its falling edge, i.e. it's an inverted 8080 WR/RD strobe,
+/// so wrapping it lets the 6800 interfaces share `Writer` and `Reader` with the 8080 ones.
+pub struct Inverted<Pin>(Pin);
+
+impl<Pin, Error> embedded_hal::digital::v2::OutputPin for Inverted<Pin>
+where
+    Pin: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    type Error = Error;
+    fn set_low(&mut self) -> Result<(), Error> {
+        self.0.set_high()
+    }
+    fn set_high(&mut self) -> Result<(), Error> {
+        self.0.set_low()
+    }
+}
+
+/// 6800 interface (CONF tied low), data is latched by the falling edge of E while R/W is low
+///
+/// The bus width follows the port's `WritePort::Word`.
+pub struct GpioWriteOnly6800Interface<Port, DC, RW, E, RST, CS> {
+    port: Port,
+    dc: DC,
+    rw: RW,
+    e: Inverted<E>,
+    rst: Option<RST>,
+    cs: Option<CS>,
+}
+
+impl<PortX, DC, RW, E, Error> GpioWriteOnly6800Interface<PortX, DC, RW, E, NoPin<Error>, NoPin<Error>>
+where
+    PortX: WritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RW: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    E: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub fn new(port: PortX, dc: DC, rw: RW, e: E) -> Self {
+        Self {
+            port,
+            dc,
+            rw,
+            e: Inverted(e),
+            rst: None,
+            cs: None,
+        }
+    }
+}
+
+impl<PortX, DC, RW, E, RST, CS, Error> GpioWriteOnly6800Interface<PortX, DC, RW, E, RST, CS>
+where
+    PortX: WritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RW: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    E: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RST: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    /// Connects the controller's RESET pin so `Ssd1963` can perform a hardware reset, it's released (driven high) right away
+    pub fn with_reset<R>(self, mut rst: R) -> Result<GpioWriteOnly6800Interface<PortX, DC, RW, E, R, CS>, Error>
+    where
+        R: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    {
+        rst.set_high()?;
+        Ok(GpioWriteOnly6800Interface {
+            port: self.port,
+            dc: self.dc,
+            rw: self.rw,
+            e: self.e,
+            rst: Some(rst),
+            cs: self.cs,
+        })
+    }
+
+    /// Connects the controller's CS pin so the bus can be shared with other peripherals
+    pub fn with_chip_select<C>(self, mut cs: C) -> Result<GpioWriteOnly6800Interface<PortX, DC, RW, E, RST, C>, Error>
+    where
+        C: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    {
+        cs.set_high()?;
+        Ok(GpioWriteOnly6800Interface {
+            port: self.port,
+            dc: self.dc,
+            rw: self.rw,
+            e: self.e,
+            rst: self.rst,
+            cs: Some(cs),
+        })
+    }
+
+    pub fn release(self) -> (PortX, DC, RW, E, Option<RST>, Option<CS>) {
+        (self.port, self.dc, self.rw, self.e.0, self.rst, self.cs)
+    }
+}
+
+impl<PortX, DC, RW, E, RST, CS, Error> WriteOnlyInterface for GpioWriteOnly6800Interface<PortX, DC, RW, E, RST, CS>
+where
+    PortX: WritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RW: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    E: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RST: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    type Port = PortX;
+    type Error = Error;
+    type DC = DC;
+    type WR = Inverted<E>;
+    type RST = RST;
+    type CS = CS;
+    fn write(&mut self) -> Result<Writer<'_, PortX, DC, Inverted<E>, CS, Error>, Error> {
+        self.e.set_high()?;
+        self.rw.set_low()?; // write
+        Ok(Writer {
+            port: &mut self.port,
+            dc: &mut self.dc,
+            wr: &mut self.e,
+            _cs: ChipSelect::assert(self.cs.as_mut())?,
+        })
+    }
+    fn reset_pin(&mut self) -> Option<&mut RST> {
+        self.rst.as_mut()
+    }
+}
+
+/// Like `GpioWriteOnly6800Interface`, but can also read the controller's memory and registers
+pub struct GpioReadWrite6800Interface<Port, DC, RW, E, RST, CS> {
+    port: Port,
+    dc: DC,
+    rw: RW,
+    e: Inverted<E>,
+    rst: Option<RST>,
+    cs: Option<CS>,
+}
+
+impl<PortX, DC, RW, E, Error> GpioReadWrite6800Interface<PortX, DC, RW, E, NoPin<Error>, NoPin<Error>>
+where
+    PortX: ReadWritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RW: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    E: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub fn new(port: PortX, dc: DC, rw: RW, e: E) -> Self {
+        Self {
+            port,
+            dc,
+            rw,
+            e: Inverted(e),
+            rst: None,
+            cs: None,
+        }
+    }
+}
+
+impl<PortX, DC, RW, E, RST, CS, Error> GpioReadWrite6800Interface<PortX, DC, RW, E, RST, CS>
+where
+    PortX: ReadWritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RW: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    E: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RST: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    /// Connects the controller's RESET pin so `Ssd1963` can perform a hardware reset, it's released (driven high) right away
+    pub fn with_reset<R>(self, mut rst: R) -> Result<GpioReadWrite6800Interface<PortX, DC, RW, E, R, CS>, Error>
+    where
+        R: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    {
+        rst.set_high()?;
+        Ok(GpioReadWrite6800Interface {
+            port: self.port,
+            dc: self.dc,
+            rw: self.rw,
+            e: self.e,
+            rst: Some(rst),
+            cs: self.cs,
+        })
+    }
+
+    /// Connects the controller's CS pin so the bus can be shared with other peripherals
+    pub fn with_chip_select<C>(self, mut cs: C) -> Result<GpioReadWrite6800Interface<PortX, DC, RW, E, RST, C>, Error>
+    where
+        C: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    {
+        cs.set_high()?;
+        Ok(GpioReadWrite6800Interface {
+            port: self.port,
+            dc: self.dc,
+            rw: self.rw,
+            e: self.e,
+            rst: self.rst,
+            cs: Some(cs),
+        })
+    }
+
+    pub fn release(self) -> (PortX, DC, RW, E, Option<RST>, Option<CS>) {
+        (self.port, self.dc, self.rw, self.e.0, self.rst, self.cs)
+    }
+}
+
+impl<PortX, DC, RW, E, RST, CS, Error> WriteOnlyInterface for GpioReadWrite6800Interface<PortX, DC, RW, E, RST, CS>
+where
+    PortX: ReadWritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RW: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    E: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RST: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    type Port = PortX;
+    type Error = Error;
+    type DC = DC;
+    type WR = Inverted<E>;
+    type RST = RST;
+    type CS = CS;
+    fn write(&mut self) -> Result<Writer<'_, PortX, DC, Inverted<E>, CS, Error>, Error> {
+        self.e.set_high()?;
+        // switch the controller's output off before driving the bus
+        self.rw.set_low()?; // write
+        self.port.dir_write();
+        Ok(Writer {
+            port: &mut self.port,
+            dc: &mut self.dc,
+            wr: &mut self.e,
+            _cs: ChipSelect::assert(self.cs.as_mut())?,
+        })
+    }
+    fn reset_pin(&mut self) -> Option<&mut RST> {
+        self.rst.as_mut()
+    }
+    fn wait_pll_lock<Delay>(&mut self, delay: &mut Delay, clocks: &Clocks) -> Result<(), crate::Error<Error>>
+    where
+        Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    {
+        crate::poll_pll_lock(self, delay, clocks)
+    }
+}
+
+impl<PortX, DC, RW, E, RST, CS, Error> ReadWriteInterface for GpioReadWrite6800Interface<PortX, DC, RW, E, RST, CS>
+where
+    PortX: ReadWritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RW: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    E: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RST: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    type Port = PortX;
+    type RD = Inverted<E>;
+    fn read(&mut self) -> Result<Reader<'_, PortX, DC, Inverted<E>, CS, Error>, Error> {
+        self.port.dir_read();
+        self.e.set_high()?;
+        self.rw.set_high()?; // read
+        let cs = ChipSelect::assert(self.cs.as_mut())?;
+        // E rises once the `Reader` has set D/C, the controller drives the bus while it's high
+        Ok(Reader::new(&mut self.port, &mut self.dc, &mut self.e, cs))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{Bus, Event, MockPin, MockPort, Pin};
+    use core::convert::Infallible;
+
+    type Interface = GpioReadWrite6800Interface<MockPort<u16>, MockPin, MockPin, MockPin, NoPin<Infallible>, MockPin>;
+
+    fn interface(bus: &Bus) -> Interface {
+        let interface = GpioReadWrite6800Interface::new(bus.port(), bus.pin(Pin::Dc), bus.pin(Pin::Rw), bus.pin(Pin::E))
+            .with_chip_select(bus.pin(Pin::Cs))
+            .unwrap();
+        bus.clear();
+        interface
+    }
+
+    #[test]
+    fn write_latches_on_falling_e() {
+        let bus = Bus::default();
+        let mut interface = interface(&bus);
+        let mut writer = interface.write().unwrap();
+        writer.command().unwrap().set_value(0x2C).unwrap().commit().unwrap();
+        drop(writer);
+        assert_eq!(
+            bus.events(),
+            [
+                Event::Pin(Pin::E, false),
+                Event::Pin(Pin::Rw, false),
+                Event::DirWrite,
+                Event::Pin(Pin::Cs, false),
+                Event::Pin(Pin::Dc, false),
+                Event::Write(0x2C),
+                Event::Pin(Pin::E, true),
+                Event::Pin(Pin::E, false),
+                Event::Pin(Pin::Cs, true),
+            ]
+        );
+        assert_eq!(bus.commands(), [(0x2C, vec![])]);
+    }
+
+    #[test]
+    fn read_sets_dc_before_e_rises() {
+        let bus = Bus::default();
+        let mut interface = interface(&bus);
+        bus.queue_reads(&[0x42]);
+        let mut getter = interface.read().unwrap().into_data().unwrap();
+        assert_eq!(getter.get_value(), Ok(0x42));
+        drop(getter);
+        assert_eq!(
+            bus.events(),
+            [
+                Event::DirRead,
+                Event::Pin(Pin::E, false),
+                Event::Pin(Pin::Rw, true),
+                Event::Pin(Pin::Cs, false),
+                Event::Pin(Pin::E, false),
+                Event::Pin(Pin::Dc, true),
+                Event::Pin(Pin::E, true),
+                Event::Read(0x42),
+                Event::Pin(Pin::E, false),
+                Event::Pin(Pin::E, true),
+                Event::Pin(Pin::E, false),
+                Event::Pin(Pin::Cs, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn command_then_data_read_switches_dc_with_e_low() {
+        let bus = Bus::default();
+        let mut interface = interface(&bus);
+        let mut reader = interface.read().unwrap();
+        reader.command().unwrap().get_value().unwrap();
+        bus.clear();
+        reader.data().unwrap();
+        assert_eq!(bus.events(), [Event::Pin(Pin::E, false), Event::Pin(Pin::Dc, true), Event::Pin(Pin::E, true)]);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::interface::{ChipSelect, NoPin, ReadWriteInterface, ReadWritePort, Reader, WriteOnlyInterface, WritePort, Writer};
+use crate::Clocks;
+
+/// 8080 interface on a 16-bit bus, see `GpioWriteOnly8080Interface`
+pub type GpioWriteOnly16BitInterface<Port, DC, WR, RST, CS> = GpioWriteOnly8080Interface<Port, DC, WR, RST, CS>;
+/// 8080 interface on a 16-bit bus, see `GpioReadWrite8080Interface`
+pub type GpioReadWrite16BitInterface<Port, DC, WR, RD, RST, CS> = GpioReadWrite8080Interface<Port, DC, WR, RD, RST, CS>;
+/// 8080 interface on an 8-bit bus, commands take one write and pixels three (red, green, blue)
+pub type GpioWriteOnly8BitInterface<Port, DC, WR, RST, CS> = GpioWriteOnly8080Interface<Port, DC, WR, RST, CS>;
+/// Like `GpioWriteOnly8BitInterface`, but can also read the controller's memory and registers
+pub type GpioReadWrite8BitInterface<Port, DC, WR, RD, RST, CS> = GpioReadWrite8080Interface<Port, DC, WR, RD, RST, CS>;
+
+/// 8080 interface (CONF tied high), data is latched by the rising edge of WR
+///
+/// The bus width follows the port's `WritePort::Word`.
+pub struct GpioWriteOnly8080Interface<Port, DC, WR, RST, CS> {
+    port: Port,
+    dc: DC,
+    wr: WR,
+    rst: Option<RST>,
+    cs: Option<CS>,
+}
+
+impl<PortX, DC, WR, Error> GpioWriteOnly8080Interface<PortX, DC, WR, NoPin<Error>, NoPin<Error>>
+where
+    PortX: WritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub fn new(port: PortX, dc: DC, wr: WR) -> Self {
+        Self {
+            port,
+            dc,
+            wr,
+            rst: None,
+            cs: None,
+        }
+    }
+}
+
+impl<PortX, DC, WR, RST, CS, Error> GpioWriteOnly8080Interface<PortX, DC, WR, RST, CS>
+where
+    PortX: WritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RST: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    /// Connects the controller's RESET pin so `Ssd1963` can perform a hardware reset, it's released (driven high) right away
+    pub fn with_reset<R>(self, mut rst: R) -> Result<GpioWriteOnly8080Interface<PortX, DC, WR, R, CS>, Error>
+    where
+        R: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    {
+        rst.set_high()?;
+        Ok(GpioWriteOnly8080Interface {
+            port: self.port,
+            dc: self.dc,
+            wr: self.wr,
+            rst: Some(rst),
+            cs: self.cs,
+        })
+    }
+
+    /// Connects the controller's CS pin so the bus can be shared with other peripherals
+    pub fn with_chip_select<C>(self, mut cs: C) -> Result<GpioWriteOnly8080Interface<PortX, DC, WR, RST, C>, Error>
+    where
+        C: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    {
+        cs.set_high()?;
+        Ok(GpioWriteOnly8080Interface {
+            port: self.port,
+            dc: self.dc,
+            wr: self.wr,
+            rst: self.rst,
+            cs: Some(cs),
+        })
+    }
+
+    pub fn release(self) -> (PortX, DC, WR, Option<RST>, Option<CS>) {
+        (self.port, self.dc, self.wr, self.rst, self.cs)
+    }
+}
+
+impl<PortX, DC, WR, RST, CS, Error> WriteOnlyInterface for GpioWriteOnly8080Interface<PortX, DC, WR, RST, CS>
+where
+    PortX: WritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RST: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    type Port = PortX;
+    type Error = Error;
+    type DC = DC;
+    type WR = WR;
+    type RST = RST;
+    type CS = CS;
+    fn write(&mut self) -> Result<Writer<'_, PortX, DC, WR, CS, Error>, Error> {
+        self.wr.set_high()?;
+        Ok(Writer {
+            port: &mut self.port,
+            dc: &mut self.dc,
+            wr: &mut self.wr,
+            _cs: ChipSelect::assert(self.cs.as_mut())?,
+        })
+    }
+    fn reset_pin(&mut self) -> Option<&mut RST> {
+        self.rst.as_mut()
+    }
+}
+
+/// Like `GpioWriteOnly8080Interface`, but can also read the controller's memory and registers
+pub struct GpioReadWrite8080Interface<Port, DC, WR, RD, RST, CS> {
+    port: Port,
+    dc: DC,
+    wr: WR,
+    rd: RD,
+    rst: Option<RST>,
+    cs: Option<CS>,
+}
+
+impl<PortX, DC, WR, RD, Error> GpioReadWrite8080Interface<PortX, DC, WR, RD, NoPin<Error>, NoPin<Error>>
+where
+    PortX: ReadWritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RD: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub fn new(port: PortX, dc: DC, wr: WR, rd: RD) -> Self {
+        Self {
+            port,
+            dc,
+            wr,
+            rd,
+            rst: None,
+            cs: None,
+        }
+    }
+}
+
+impl<PortX, DC, WR, RD, RST, CS, Error> GpioReadWrite8080Interface<PortX, DC, WR, RD, RST, CS>
+where
+    PortX: ReadWritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RD: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RST: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    /// Connects the controller's RESET pin so `Ssd1963` can perform a hardware reset, it's released (driven high) right away
+    pub fn with_reset<R>(self, mut rst: R) -> Result<GpioReadWrite8080Interface<PortX, DC, WR, RD, R, CS>, Error>
+    where
+        R: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    {
+        rst.set_high()?;
+        Ok(GpioReadWrite8080Interface {
+            port: self.port,
+            dc: self.dc,
+            wr: self.wr,
+            rd: self.rd,
+            rst: Some(rst),
+            cs: self.cs,
+        })
+    }
+
+    /// Connects the controller's CS pin so the bus can be shared with other peripherals
+    pub fn with_chip_select<C>(self, mut cs: C) -> Result<GpioReadWrite8080Interface<PortX, DC, WR, RD, RST, C>, Error>
+    where
+        C: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    {
+        cs.set_high()?;
+        Ok(GpioReadWrite8080Interface {
+            port: self.port,
+            dc: self.dc,
+            wr: self.wr,
+            rd: self.rd,
+            rst: self.rst,
+            cs: Some(cs),
+        })
+    }
+
+    pub fn release(self) -> (PortX, DC, WR, RD, Option<RST>, Option<CS>) {
+        (self.port, self.dc, self.wr, self.rd, self.rst, self.cs)
+    }
+}
+
+impl<PortX, DC, WR, RD, RST, CS, Error> WriteOnlyInterface for GpioReadWrite8080Interface<PortX, DC, WR, RD, RST, CS>
+where
+    PortX: ReadWritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RD: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RST: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    type Port = PortX;
+    type Error = Error;
+    type DC = DC;
+    type WR = WR;
+    type RST = RST;
+    type CS = CS;
+    fn write(&mut self) -> Result<Writer<'_, PortX, DC, WR, CS, Error>, Error> {
+        self.wr.set_high()?;
+        self.rd.set_high()?;
+        self.port.dir_write();
+        Ok(Writer {
+            port: &mut self.port,
+            dc: &mut self.dc,
+            wr: &mut self.wr,
+            _cs: ChipSelect::assert(self.cs.as_mut())?,
+        })
+    }
+    fn reset_pin(&mut self) -> Option<&mut RST> {
+        self.rst.as_mut()
+    }
+    fn wait_pll_lock<Delay>(&mut self, delay: &mut Delay, clocks: &Clocks) -> Result<(), crate::Error<Error>>
+    where
+        Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    {
+        crate::poll_pll_lock(self, delay, clocks)
+    }
+}
+
+impl<PortX, DC, WR, RD, RST, CS, Error> ReadWriteInterface for GpioReadWrite8080Interface<PortX, DC, WR, RD, RST, CS>
+where
+    PortX: ReadWritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RD: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RST: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    type Port = PortX;
+    type RD = RD;
+    fn read(&mut self) -> Result<Reader<'_, PortX, DC, RD, CS, Error>, Error> {
+        self.port.dir_read();
+        self.wr.set_high()?; // maybe not needed
+        let cs = ChipSelect::assert(self.cs.as_mut())?;
+        // RD falls once the `Reader` has set D/C
+        Ok(Reader::new(&mut self.port, &mut self.dc, &mut self.rd, cs))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{Bus, Event, MockPin, MockPort, Pin};
+    use core::convert::Infallible;
+
+    type Interface = GpioReadWrite16BitInterface<MockPort<u16>, MockPin, MockPin, MockPin, NoPin<Infallible>, MockPin>;
+
+    fn interface(bus: &Bus) -> Interface {
+        let interface = GpioReadWrite16BitInterface::new(bus.port(), bus.pin(Pin::Dc), bus.pin(Pin::Wr), bus.pin(Pin::Rd))
+            .with_chip_select(bus.pin(Pin::Cs))
+            .unwrap();
+        assert_eq!(bus.events(), [Event::Pin(Pin::Cs, true)]);
+        bus.clear();
+        interface
+    }
+
+    #[test]
+    fn chip_select_spans_writer() {
+        let bus = Bus::default();
+        let mut interface = interface(&bus);
+        let mut writer = interface.write().unwrap();
+        writer.data().unwrap().set_value(0x1234).unwrap().commit().unwrap();
+        assert_eq!(
+            bus.events(),
+            [
+                Event::Pin(Pin::Wr, true),
+                Event::Pin(Pin::Rd, true),
+                Event::DirWrite,
+                Event::Pin(Pin::Cs, false),
+                Event::Pin(Pin::Dc, true),
+                Event::Write(0x1234),
+                Event::Pin(Pin::Wr, false),
+                Event::Pin(Pin::Wr, true),
+            ]
+        );
+        drop(writer);
+        assert_eq!(bus.events().last(), Some(&Event::Pin(Pin::Cs, true)));
+    }
+
+    #[test]
+    fn read_ends_with_rd_high_before_chip_select() {
+        let bus = Bus::default();
+        let mut interface = interface(&bus);
+        bus.queue_reads(&[0x42]);
+        let mut getter = interface.read().unwrap().into_data().unwrap();
+        assert_eq!(getter.get_value(), Ok(0x42));
+        drop(getter);
+        assert_eq!(
+            bus.events(),
+            [
+                Event::DirRead,
+                Event::Pin(Pin::Wr, true),
+                Event::Pin(Pin::Cs, false),
+                Event::Pin(Pin::Rd, true),
+                Event::Pin(Pin::Dc, true),
+                Event::Pin(Pin::Rd, false),
+                Event::Read(0x42),
+                Event::Pin(Pin::Rd, true),
+                Event::Pin(Pin::Rd, false),
+                Event::Pin(Pin::Rd, true),
+                Event::Pin(Pin::Cs, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn borrowed_getter_keeps_transaction() {
+        let bus = Bus::default();
+        let mut interface = interface(&bus);
+        let mut reader = interface.read().unwrap();
+        reader.data().unwrap().get_value().unwrap();
+        assert_eq!(bus.events().last(), Some(&Event::Pin(Pin::Rd, false)));
+        drop(reader);
+        let events = bus.events();
+        assert_eq!(events[events.len() - 2..], [Event::Pin(Pin::Rd, true), Event::Pin(Pin::Cs, true)]);
+    }
+}
@@ -0,0 +1,346 @@
+use crate::pixel_format::{PixelFormat, B16Format565, B24, B8};
+use crate::{Clocks, Error};
+use core::marker::PhantomData;
+
+/// A value transferred by a single WR or RD strobe, determines the width of the data bus
+pub trait BusWord: Copy + Default + From<u8> {
+    /// Pixel format `Ssd1963` uses unless told otherwise
+    type DefaultFormat: PixelFormat<Word = Self>;
+    /// Commands and their parameters are always transferred on D[7:0]
+    fn low_byte(self) -> u8;
+}
+
+impl BusWord for u8 {
+    type DefaultFormat = B8;
+    fn low_byte(self) -> u8 {
+        self
+    }
+}
+
+impl BusWord for u16 {
+    type DefaultFormat = B16Format565;
+    fn low_byte(self) -> u8 {
+        self as u8
+    }
+}
+
+/// 18-bit and 24-bit buses
+impl BusWord for u32 {
+    type DefaultFormat = B24;
+    fn low_byte(self) -> u8 {
+        self as u8
+    }
+}
+
+pub trait WritePort {
+    type Word: BusWord;
+    fn set_value(&mut self, value: Self::Word);
+}
+
+pub trait ReadWritePort: WritePort {
+    fn get_value(&mut self) -> Self::Word;
+    fn dir_write(&mut self);
+    fn dir_read(&mut self);
+}
+
+/// Keeps CS asserted while alive and releases it on drop
+pub struct ChipSelect<'a, CS, Error>
+where
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    cs: Option<&'a mut CS>,
+}
+
+impl<'a, CS, Error> ChipSelect<'a, CS, Error>
+where
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub(crate) fn assert(mut cs: Option<&'a mut CS>) -> Result<Self, Error> {
+        if let Some(cs) = cs.as_mut() {
+            cs.set_low()?;
+        }
+        Ok(Self { cs })
+    }
+}
+
+impl<'a, CS, Error> Drop for ChipSelect<'a, CS, Error>
+where
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    fn drop(&mut self) {
+        if let Some(cs) = self.cs.as_mut() {
+            // nothing sensible to do with an error here, the next transaction asserts CS again anyway
+            let _ = cs.set_high();
+        }
+    }
+}
+
+#[must_use]
+pub struct Committer<'a, WR, Error>
+where
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    wr: &'a mut WR,
+}
+
+impl<'a, WR, Error> Committer<'a, WR, Error>
+where
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub fn commit(&mut self) -> Result<(), Error> {
+        self.wr.set_low()?;
+        self.wr.set_high()?;
+        Ok(())
+    }
+}
+
+pub struct ValueSetter<'a, PortX, WR, Error>
+where
+    PortX: WritePort,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    port: &'a mut PortX,
+    wr: &'a mut WR,
+}
+
+impl<'a, PortX, WR, Error> ValueSetter<'a, PortX, WR, Error>
+where
+    PortX: WritePort,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub fn set_value(&mut self, value: PortX::Word) -> Result<Committer<WR, Error>, Error> {
+        self.port.set_value(value);
+        Ok(Committer { wr: self.wr })
+    }
+}
+
+pub struct Writer<'a, PortX, DC, WR, CS, Error>
+where
+    PortX: WritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub(crate) port: &'a mut PortX,
+    pub(crate) dc: &'a mut DC,
+    pub(crate) wr: &'a mut WR,
+    pub(crate) _cs: ChipSelect<'a, CS, Error>,
+}
+
+impl<'a, PortX, DC, WR, CS, Error> Writer<'a, PortX, DC, WR, CS, Error>
+where
+    PortX: WritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    WR: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub fn command(&mut self) -> Result<ValueSetter<PortX, WR, Error>, Error> {
+        self.dc.set_low()?;
+        Ok(ValueSetter {
+            port: self.port,
+            wr: self.wr,
+        })
+    }
+
+    pub fn data(&mut self) -> Result<ValueSetter<PortX, WR, Error>, Error> {
+        self.dc.set_high()?;
+        Ok(ValueSetter {
+            port: self.port,
+            wr: self.wr,
+        })
+    }
+}
+
+/// RD and CS of a read transaction, RD goes back high before CS is released
+pub struct ReadStrobe<'a, RD, CS, Error>
+where
+    RD: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    rd: &'a mut RD,
+    /// `None` while borrowed from a `Reader`, which ends the transaction itself
+    cs: Option<ChipSelect<'a, CS, Error>>,
+}
+
+impl<'a, RD, CS, Error> Drop for ReadStrobe<'a, RD, CS, Error>
+where
+    RD: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    fn drop(&mut self) {
+        if self.cs.is_some() {
+            // leave the bus to other peripherals idle, as with CS an error can't be acted on here
+            let _ = self.rd.set_high();
+        }
+    }
+}
+
+pub struct ValueGetter<'a, PortX, RD, CS, Error>
+where
+    PortX: ReadWritePort,
+    RD: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    port: &'a mut PortX,
+    /// Taken over from the `Reader` by `into_command`/`into_data`
+    strobe: ReadStrobe<'a, RD, CS, Error>,
+}
+
+impl<'a, PortX, RD, CS, Error> ValueGetter<'a, PortX, RD, CS, Error>
+where
+    PortX: ReadWritePort,
+    RD: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub fn get_value(&mut self) -> Result<PortX::Word, Error> {
+        let value = self.port.get_value();
+        self.strobe.rd.set_high()?;
+        self.strobe.rd.set_low()?;
+        Ok(value)
+    }
+}
+
+/// A read transaction, CS is released and RD left high when it's dropped
+pub struct Reader<'a, PortX, DC, RD, CS, Error>
+where
+    PortX: ReadWritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RD: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    port: &'a mut PortX,
+    dc: &'a mut DC,
+    strobe: ReadStrobe<'a, RD, CS, Error>,
+}
+
+impl<'a, PortX, DC, RD, CS, Error> Reader<'a, PortX, DC, RD, CS, Error>
+where
+    PortX: ReadWritePort,
+    DC: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    RD: embedded_hal::digital::v2::OutputPin<Error = Error>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = Error>,
+{
+    pub(crate) fn new(port: &'a mut PortX, dc: &'a mut DC, rd: &'a mut RD, cs: ChipSelect<'a, CS, Error>) -> Self {
+        Self {
+            port,
+            dc,
+            strobe: ReadStrobe { rd, cs: Some(cs) },
+        }
+    }
+
+    /// Sets D/C with RD inactive, then starts the first read cycle, so D/C is stable when RD falls
+    fn select(&mut self, data: bool) -> Result<(), Error> {
+        self.strobe.rd.set_high()?;
+        if data {
+            self.dc.set_high()?;
+        } else {
+            self.dc.set_low()?;
+        }
+        self.strobe.rd.set_low()
+    }
+
+    fn borrow_getter(&mut self) -> ValueGetter<'_, PortX, RD, CS, Error> {
+        ValueGetter {
+            port: self.port,
+            strobe: ReadStrobe {
+                rd: self.strobe.rd,
+                cs: None,
+            },
+        }
+    }
+
+    pub fn command(&mut self) -> Result<ValueGetter<PortX, RD, CS, Error>, Error> {
+        self.select(false)?;
+        Ok(self.borrow_getter())
+    }
+    pub fn into_command<'b>(mut self) -> Result<ValueGetter<'b, PortX, RD, CS, Error>, Error>
+    where
+        'a: 'b,
+    {
+        self.select(false)?;
+        Ok(ValueGetter {
+            port: self.port,
+            strobe: self.strobe,
+        })
+    }
+
+    pub fn data(&mut self) -> Result<ValueGetter<PortX, RD, CS, Error>, Error> {
+        self.select(true)?;
+        Ok(self.borrow_getter())
+    }
+    pub fn into_data<'b>(mut self) -> Result<ValueGetter<'b, PortX, RD, CS, Error>, Error>
+    where
+        'a: 'b,
+    {
+        self.select(true)?;
+        Ok(ValueGetter {
+            port: self.port,
+            strobe: self.strobe,
+        })
+    }
+}
+
+/// Stands in for an optional pin that isn't connected
+pub struct NoPin<Error>(PhantomData<Error>);
+
+impl<Error> embedded_hal::digital::v2::OutputPin for NoPin<Error> {
+    type Error = Error;
+    fn set_low(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn set_high(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+pub type InterfaceWriter<'a, I> = Writer<
+    'a,
+    <I as WriteOnlyInterface>::Port,
+    <I as WriteOnlyInterface>::DC,
+    <I as WriteOnlyInterface>::WR,
+    <I as WriteOnlyInterface>::CS,
+    <I as WriteOnlyInterface>::Error,
+>;
+pub type InterfaceReader<'a, I> = Reader<
+    'a,
+    <I as ReadWriteInterface>::Port,
+    <I as WriteOnlyInterface>::DC,
+    <I as ReadWriteInterface>::RD,
+    <I as WriteOnlyInterface>::CS,
+    <I as WriteOnlyInterface>::Error,
+>;
+
+pub trait WriteOnlyInterface {
+    type Port: WritePort;
+    type Error;
+    type DC: embedded_hal::digital::v2::OutputPin<Error = Self::Error>;
+    type WR: embedded_hal::digital::v2::OutputPin<Error = Self::Error>;
+    type RST: embedded_hal::digital::v2::OutputPin<Error = Self::Error>;
+    type CS: embedded_hal::digital::v2::OutputPin<Error = Self::Error>;
+    /// Starts a write transaction, CS stays asserted until the `Writer` is dropped
+    fn write(&mut self) -> Result<InterfaceWriter<Self>, Self::Error>;
+    /// The controller's active low RESET pin, if it's connected
+    fn reset_pin(&mut self) -> Option<&mut Self::RST>;
+    /// Waits for the controller's PLL to lock after it was enabled, by default for `Clocks::lock_wait_us`
+    ///
+    /// The driver only knows the interface as write-only at that point, so the default can't poll even if the bus
+    /// can read. Every implementation of `ReadWriteInterface` has to override this with `crate::poll_pll_lock`,
+    /// which polls `get_pll_status` for at most `Clocks::lock_timeout_us`.
+    fn wait_pll_lock<Delay>(&mut self, delay: &mut Delay, clocks: &Clocks) -> Result<(), Error<Self::Error>>
+    where
+        Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    {
+        crate::delay_us(delay, clocks.lock_wait_us);
+        Ok(())
+    }
+}
+
+/// A bus that can also read from the controller
+///
+/// Implementations must override `WriteOnlyInterface::wait_pll_lock` to poll the PLL, see there.
+pub trait ReadWriteInterface: WriteOnlyInterface {
+    type Port: ReadWritePort<Word = <<Self as WriteOnlyInterface>::Port as WritePort>::Word>;
+    type RD: embedded_hal::digital::v2::OutputPin<Error = Self::Error>;
+    /// Starts a read transaction, CS stays asserted until the `Reader` (or the `ValueGetter` it was turned into) is dropped
+    fn read(&mut self) -> Result<InterfaceReader<Self>, Self::Error>;
+}
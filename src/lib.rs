@@ -2,36 +2,188 @@
 #![feature(generic_associated_types)]
 #![allow(incomplete_features)]
 
+pub mod backlight;
 mod bit_twiddling;
 mod bounds;
-mod commands;
+pub mod color;
+pub mod commands;
 pub mod display;
-mod gpio16bit_interface;
+mod error;
+pub mod gpio;
+mod gpio6800_interface;
+mod gpio8080_interface;
+mod interface;
+#[cfg(test)]
+mod mock;
+pub mod orientation;
+pub mod panel;
+pub mod pixel_format;
+pub mod pll;
+pub mod power;
+mod screen;
+pub mod scroll;
 
+pub use backlight::{Backlight, Dbc};
 pub use bounds::Bounds;
 
-use commands::{CommandCode, CommandData};
+use commands::{CommandCode, CommandData, GpioRop, LcdGen, Query, SetDbcTh, SetPostProc};
+pub use commands::set_gamma_curve::GammaCurve;
+pub use commands::set_tear_on::TearingMode;
 use core::fmt::Debug;
-use core::{cmp::min, convert::TryFrom, ops::RangeBounds};
+use embedded_hal::digital::v2::{InputPin, OutputPin};
+use core::{cmp::min, convert::TryFrom, marker::PhantomData, ops::{RangeBounds, RangeInclusive}};
 use display::CopyArea;
 pub use display::Display;
-pub use gpio16bit_interface::{GpioReadWrite16BitInterface, GpioWriteOnly16BitInterface, WriteOnlyInterface};
-use gpio16bit_interface::{ReadWriteInterface, ValueGetter, Writer};
+pub use gpio6800_interface::{GpioReadWrite6800Interface, GpioWriteOnly6800Interface, Inverted};
+pub use gpio8080_interface::{
+    GpioReadWrite16BitInterface, GpioReadWrite8080Interface, GpioReadWrite8BitInterface, GpioWriteOnly16BitInterface,
+    GpioWriteOnly8080Interface, GpioWriteOnly8BitInterface,
+};
+pub use interface::{BusWord, NoPin, ReadWritePort, WriteOnlyInterface, WritePort};
+use interface::{ReadWriteInterface, ValueGetter, Writer};
+pub use error::Error;
+pub use gpio::{Gpio, GpioPin};
+use gpio::GpioState;
+pub use orientation::{Orientation, Rotation};
+pub use panel::PanelConfig;
+pub use pixel_format::PixelFormat;
+pub use pll::Clocks;
+pub use power::PowerState;
+pub use screen::{Lcd480x272, Lcd640x480, Lcd800x480, Lcd864x480, Screen};
+pub use scroll::ScrollRegion;
+use scroll::ScrollError;
+
+/// Crystal frequency assumed by `Ssd1963::new`
+pub const DEFAULT_REFERENCE_CLOCK: u32 = 10_000_000;
+/// System clock `Ssd1963::new` configures the PLL for
+pub const DEFAULT_SYSTEM_CLOCK: u32 = 100_000_000;
+
+/// How long RESET is held low during a hardware reset
+const RESET_PULSE_US: u32 = 100;
+/// How long the controller needs after RESET is released before it accepts commands
+const RESET_RECOVERY_US: u32 = 5_000;
+/// How long the controller needs after entering or leaving sleep mode before it accepts the next command
+const SLEEP_WAIT_US: u32 = 5_000;
+/// Interval between two samples of TE or the scanline while waiting for the vertical blanking
+const VBLANK_POLL_US: u8 = 10;
+
+/// Size of the controller's frame buffer (1215 KB), it always keeps 24 bits per pixel whatever the pixel data format
+const FRAME_BUFFER_BYTES: u32 = 1_244_160;
+
+/// Word carried by the data bus of `Interface`
+type PortWord<Interface> = <<Interface as WriteOnlyInterface>::Port as WritePort>::Word;
+/// A driver handed back together with the bus error that stopped a conversion
+type Returned<Driver, Interface> = (Driver, Error<<Interface as WriteOnlyInterface>::Error>);
+
+fn delay_us<Delay>(delay: &mut Delay, mut us: u32)
+where
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+{
+    while us > 0 {
+        let step = min(us, u32::from(u8::MAX));
+        delay.delay_us(step as u8);
+        us -= step;
+    }
+}
+
+fn send_command<PortX, DC, WR, CS, E, Delay, Command, const N: usize>(
+    writer: &mut Writer<'_, PortX, DC, WR, CS, E>,
+    delay: &mut Delay,
+    command: &Command,
+) -> Result<(), E>
+where
+    PortX: WritePort,
+    DC: OutputPin<Error = E>,
+    WR: OutputPin<Error = E>,
+    CS: OutputPin<Error = E>,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    Command: CommandCode + CommandData<N>,
+{
+    let mut w = writer.command()?;
+    let mut comitter = w.set_value(Command::CODE.into())?;
+    delay.delay_us(1);
+    comitter.commit()?;
+    let mut d = writer.data()?;
+    for data in command.data() {
+        let mut comitter = d.set_value(data.into())?;
+        delay.delay_us(1);
+        comitter.commit()?;
+    }
+    Ok(())
+}
+
+fn read_response<Cmd, Interface, Delay>(interface: &mut Interface, delay: &mut Delay) -> Result<Cmd::Response, Error<Interface::Error>>
+where
+    Cmd: Query,
+    Interface: ReadWriteInterface,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+{
+    send_command(&mut interface.write().map_err(Error::Interface)?, delay, &Cmd::default()).map_err(Error::Interface)?;
+    let mut getter = interface.read().and_then(|reader| reader.into_data()).map_err(Error::Interface)?;
+    Cmd::read_response(|| getter.get_value().map(BusWord::low_byte))
+        .map_err(Error::Interface)?
+        .ok_or(Error::InvalidResponse)
+}
+
+/// Polls `get_pll_status` until the PLL locks, for at most `Clocks::lock_timeout_us`
+///
+/// Read-write interfaces implement `WriteOnlyInterface::wait_pll_lock` with it.
+pub fn poll_pll_lock<Interface, Delay>(interface: &mut Interface, delay: &mut Delay, clocks: &Clocks) -> Result<(), Error<Interface::Error>>
+where
+    Interface: ReadWriteInterface,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+{
+    const POLL_INTERVAL_US: u8 = 10;
+    let mut waited = 0;
+    loop {
+        if read_response::<commands::GetPllStatus, _, _>(interface, delay)?.locked {
+            return Ok(());
+        }
+        if waited >= clocks.lock_timeout_us {
+            return Err(Error::PllLockTimeout);
+        }
+        delay.delay_us(POLL_INTERVAL_US);
+        waited += u32::from(POLL_INTERVAL_US);
+    }
+}
 
 #[cfg(feature = "stm32f1xx")]
 #[path = "features/stm32f1xx.rs"]
 pub mod stm32f1xx;
 
-pub struct Ssd1963<Lcd, Delay, Interface>
+#[cfg(feature = "graphics")]
+#[path = "features/embedded_graphics.rs"]
+pub mod embedded_graphics;
+
+/// Driver for a SSD1963 attached through `Interface`
+///
+/// `Format` decides how pixels are transferred and thus the color type, see `with_pixel_format`.
+pub struct Ssd1963<Lcd, Delay, Interface, Format = <PortWord<Interface> as BusWord>::DefaultFormat>
 where
     Lcd: Screen,
     Delay: embedded_hal::blocking::delay::DelayUs<u8>,
     Interface: WriteOnlyInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
 {
     interface: Interface,
     pub delay: Delay,
     #[allow(dead_code)]
     lcd: Lcd,
+    config: PanelConfig,
+    clocks: Clocks,
+    scroll: ScrollRegion,
+    scroll_offset: u16,
+    /// First frame memory row of the hidden frame while double buffering
+    back_buffer: Option<u16>,
+    orientation: Orientation,
+    backlight: Backlight,
+    dbc: Dbc,
+    gpio: GpioState,
+    power: PowerState,
+    /// First and last frame memory row shown in partial display mode
+    partial: Option<(u16, u16)>,
+    gamma: GammaCurve,
+    format: PhantomData<Format>,
 }
 
 impl<Lcd, Delay, Interface> Ssd1963<Lcd, Delay, Interface>
@@ -40,178 +192,617 @@ where
     Delay: embedded_hal::blocking::delay::DelayUs<u8>,
     Interface: WriteOnlyInterface,
 {
-    fn send_command<'c, 'i: 'c, Command, const N: usize>(
-        writer: &'i mut Writer<Interface::Port, Interface::DC, Interface::WR, Interface::Error>,
-        delay: &mut Delay,
-        command: &'c Command,
-    ) -> Result<(), Interface::Error>
+    /// Initializes the controller for `Lcd` clocked from a `DEFAULT_REFERENCE_CLOCK` crystal
+    pub fn new(lcd: Lcd, interface: Interface, delay: Delay) -> Result<Self, Error<Interface::Error>> {
+        let clocks = Clocks::new(DEFAULT_REFERENCE_CLOCK, DEFAULT_SYSTEM_CLOCK, Lcd::PIXEL_CLOCK)?;
+        Self::new_with_config(lcd, &Lcd::CONFIG, &clocks, interface, delay)
+    }
+
+    /// Like `new`, but with panel timing and clocks chosen at runtime instead of taken from `Lcd`
+    ///
+    /// The pixel clock is the one `clocks` generates, `Lcd::PIXEL_CLOCK` isn't used.
+    ///
+    /// Read-write interfaces poll `get_pll_status` until the PLL locks before it's used as system clock,
+    /// write-only ones give it `Clocks::lock_wait_us`, see `WriteOnlyInterface::wait_pll_lock`.
+    pub fn new_with_config(
+        lcd: Lcd,
+        config: &PanelConfig,
+        clocks: &Clocks,
+        interface: Interface,
+        delay: Delay,
+    ) -> Result<Self, Error<Interface::Error>> {
+        Self::start(lcd, config, clocks, interface, delay)
+    }
+}
+
+impl<Lcd, Delay, Interface, Format> Ssd1963<Lcd, Delay, Interface, Format>
+where
+    Lcd: Screen,
+    Delay: embedded_hal::blocking::delay::DelayUs<u8>,
+    Interface: WriteOnlyInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
+{
+    fn start(
+        lcd: Lcd,
+        config: &PanelConfig,
+        clocks: &Clocks,
+        mut interface: Interface,
+        mut delay: Delay,
+    ) -> Result<Self, Error<Interface::Error>> {
+        config.validate(Lcd::WIDTH, Lcd::HEIGHT)?;
+        Self::hardware_reset(&mut interface, &mut delay).map_err(Error::Interface)?;
+        Self::configure(&mut interface, &mut delay, config, clocks)?;
+        Ok(Self {
+            interface,
+            lcd,
+            delay,
+            config: *config,
+            clocks: *clocks,
+            scroll: ScrollRegion::full(Lcd::HEIGHT),
+            scroll_offset: 0,
+            back_buffer: None,
+            orientation: Orientation::default(),
+            backlight: Backlight::default(),
+            dbc: Dbc::default(),
+            gpio: GpioState::default(),
+            power: PowerState::Normal,
+            partial: None,
+            gamma: GammaCurve::default(),
+            format: PhantomData,
+        })
+    }
+
+    /// Switches the host interface to another pixel format, the panel itself isn't affected
+    ///
+    /// On a bus error the driver is handed back unchanged along with the error.
+    pub fn with_pixel_format<F>(mut self) -> Result<Ssd1963<Lcd, Delay, Interface, F>, Returned<Self, Interface>>
     where
-        Command: CommandCode + CommandData<N>,
+        F: PixelFormat<Word = PortWord<Interface>>,
     {
-        let mut w = writer.command()?;
-        let mut comitter = w.set_value(Command::CODE.into())?;
-        delay.delay_us(1);
-        comitter.commit()?;
-        let mut d = writer.data()?;
-        for data in command.data() {
-            let mut comitter = d.set_value(data.into())?;
-            delay.delay_us(1);
-            comitter.commit()?;
+        let command = commands::SetPixelDataInterface {
+            pixel_data_interface_format: F::FORMAT,
+        };
+        let sent = match self.interface.write() {
+            Ok(mut writer) => send_command(&mut writer, &mut self.delay, &command),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = sent {
+            return Err((self, Error::Interface(err)));
+        }
+        Ok(Ssd1963 {
+            interface: self.interface,
+            delay: self.delay,
+            lcd: self.lcd,
+            config: self.config,
+            clocks: self.clocks,
+            scroll: self.scroll,
+            scroll_offset: self.scroll_offset,
+            back_buffer: self.back_buffer,
+            orientation: self.orientation,
+            backlight: self.backlight,
+            dbc: self.dbc,
+            gpio: self.gpio,
+            power: self.power,
+            partial: self.partial,
+            gamma: self.gamma,
+            format: PhantomData,
+        })
+    }
+
+    /// Pulses the RESET pin and reinitializes the controller
+    ///
+    /// Without a reset pin on the interface only the software reset that's part of the init sequence is performed.
+    pub fn hard_reset(&mut self) -> Result<(), Error<Interface::Error>> {
+        Self::hardware_reset(&mut self.interface, &mut self.delay).map_err(Error::Interface)?;
+        self.scroll = ScrollRegion::full(Lcd::HEIGHT);
+        self.scroll_offset = 0;
+        self.back_buffer = None;
+        self.orientation = Orientation::default();
+        self.backlight = Backlight::default();
+        self.dbc = Dbc::default();
+        self.gpio = GpioState::default();
+        self.power = PowerState::Normal;
+        self.partial = None;
+        self.gamma = GammaCurve::default();
+        Self::configure(&mut self.interface, &mut self.delay, &self.config, &self.clocks)
+    }
+
+    fn hardware_reset(interface: &mut Interface, delay: &mut Delay) -> Result<(), Interface::Error> {
+        if let Some(rst) = interface.reset_pin() {
+            rst.set_low()?;
+            delay_us(delay, RESET_PULSE_US);
+            rst.set_high()?;
+            delay_us(delay, RESET_RECOVERY_US);
         }
         Ok(())
     }
 
-    pub fn new(lcd: Lcd, mut interface: Interface, mut delay: Delay) -> Result<Self, Interface::Error> {
+    fn configure(
+        interface: &mut Interface,
+        delay: &mut Delay,
+        config: &PanelConfig,
+        clocks: &Clocks,
+    ) -> Result<(), Error<Interface::Error>> {
+        Self::start_pll(interface, delay, clocks).map_err(Error::Interface)?;
+        interface.wait_pll_lock(delay, clocks)?;
+        Self::init(interface, delay, config, clocks).map_err(Error::Interface)
+    }
+
+    fn start_pll(interface: &mut Interface, delay: &mut Delay, clocks: &Clocks) -> Result<(), Interface::Error> {
         let mut writer = interface.write()?;
-        Self::send_command(
+        // M and N may only change with the PLL stopped, which it isn't when reinitializing without a hardware reset
+        send_command(
             &mut writer,
-            &mut delay,
-            &commands::SetPllMn {
-                pll_multiplier: 0x1E,
-                pll_divider: 0x02.into(),
-                use_multiplier_and_divider: true,
+            delay,
+            &commands::SetPll {
+                system_clock_source: commands::set_pll::SystemClockSource::ReferenceClock,
+                enable_pll: false,
             },
         )?;
-        Self::send_command(
+        send_command(&mut writer, delay, &clocks.pll_mn())?;
+        send_command(
             &mut writer,
-            &mut delay,
+            delay,
             &commands::SetPll {
                 system_clock_source: commands::set_pll::SystemClockSource::ReferenceClock,
                 enable_pll: true,
             },
-        )?;
-        Self::send_command(
+        )
+    }
+
+    fn init(interface: &mut Interface, delay: &mut Delay, config: &PanelConfig, clocks: &Clocks) -> Result<(), Interface::Error> {
+        let mut writer = interface.write()?;
+        send_command(
             &mut writer,
-            &mut delay,
+            delay,
             &commands::SetPll {
                 system_clock_source: commands::set_pll::SystemClockSource::PllOutput,
                 enable_pll: true,
             },
         )?;
-        Self::send_command(&mut writer, &mut delay, &commands::SoftReset)?;
+        send_command(&mut writer, delay, &commands::SoftReset)?;
         delay.delay_us(1);
-        Self::send_command(
+        send_command(&mut writer, delay, &clocks.lshift_freq())?;
+        send_command(&mut writer, delay, &config.lcd_mode(Lcd::WIDTH, Lcd::HEIGHT))?;
+        send_command(&mut writer, delay, &config.hori_period())?;
+        send_command(&mut writer, delay, &config.vert_period())?;
+        send_command(&mut writer, delay, &Orientation::default().address_mode())?;
+        send_command(
             &mut writer,
-            &mut delay,
-            &commands::SetLShiftFreq {
-                lcdc_fpr: 0b11_11111111_11111111.into(),
-            },
-        )?;
-        Self::send_command(
-            &mut writer,
-            &mut delay,
-            &commands::SetLcdMode {
-                data_width: commands::set_lcd_mode::TftPanelDataWidth::B24,
-                color_depth_enhancement_enable: false,
-                frc_enable: false,
-                lshift_polarity: commands::set_lcd_mode::Edge::Falling,
-                lline_polarity: commands::set_lcd_mode::Active::ActiveLow,
-                lframe_polarity: commands::set_lcd_mode::Active::ActiveLow,
-                tft_type: commands::set_lcd_mode::TftType::TftMode0,
-                hdp: (Lcd::WIDTH - 1).into(),
-                vdp: (Lcd::HEIGHT - 1).into(),
-                even_line_color_sequence: commands::set_lcd_mode::ColorSequence::Rgb,
-                odd_line_color_sequence: commands::set_lcd_mode::ColorSequence::Rgb,
-            },
-        )?;
-        Self::send_command(
-            &mut writer,
-            &mut delay,
-            &commands::SetHoriPeriod {
-                ht: 928.into(),
-                hps: 46.into(),
-                hpw: 48.into(),
-                lps: 15.into(),
-                lpspp: 0.into(),
-            },
-        )?;
-        Self::send_command(
-            &mut writer,
-            &mut delay,
-            &commands::SetVertPeriod {
-                vt: 525.into(),
-                vps: 16.into(),
-                vpw: 16.into(),
-                fps: 8.into(),
-            },
-        )?;
-        // commands::set_gpio_value::SetGpioValue {
-        //     gpio3_value: commands::set_gpio_value::Output::One,
-        //     gpio2_value: commands::set_gpio_value::Output::One,
-        //     gpio1_value: commands::set_gpio_value::Output::One,
-        //     gpio0_value: commands::set_gpio_value::Output::One,
-        // }.send(&mut interface)?;
-        // commands::set_gpio_conf::SetGpioConf {
-        //     gpio3_conf: commands::ControlledBy::Host,
-        //     gpio2_conf: commands::ControlledBy::Host,
-        //     gpio1_conf: commands::ControlledBy::Host,
-        //     gpio0_conf: commands::ControlledBy::Host,
-        //     gpio3_dir: commands::set_gpio_conf::Direction::Input,
-        //     gpio2_dir: commands::set_gpio_conf::Direction::Output,
-        //     gpio1_dir: commands::set_gpio_conf::Direction::Output,
-        //     gpio0_dir: commands::set_gpio_conf::Direction::Output,
-        //     gpio0_power_control: commands::set_gpio_conf::PowerControl::Normal,
-        // }
-        Self::send_command(
-            &mut writer,
-            &mut delay,
-            &commands::SetAddressMode {
-                page_address_order: commands::set_address_mode::PageAddressOrder::TopToBottom,
-                column_address_order: commands::set_address_mode::ColumnAddressOrder::LeftToRight,
-                page_column_order: commands::set_address_mode::PageColumnOrder::Normal,
-                line_address_order: commands::set_address_mode::LineAddressOrder::LcdRefreshTopToBottom,
-                color_order: commands::set_address_mode::ColorOrder::Rgb,
-                data_latch_order: commands::set_address_mode::DataLatchOrder::LcdRefreshLeftToRight,
-                flip_horizontal: false,
-                flip_vertical: false,
-            },
-        )?;
-        Self::send_command(
-            &mut writer,
-            &mut delay,
+            delay,
             &commands::SetPixelDataInterface {
-                pixel_data_interface_format: commands::set_pixel_data_interface::PixelDataInterfaceFormat::B16Format565,
+                pixel_data_interface_format: Format::FORMAT,
             },
         )?;
-        // interface.write_command(0xB8u8)?;
-        // interface.write_data(0x0fu8)?; //GPIO is controlled by host GPIO[3:0]=output   GPIO[0]=1  LCD ON  GPIO[0]=1  LCD OFF
-        // interface.write_data(0x01u8)?; //GPIO0 normal
-        // interface.write_command(0xBAu8)?;
-        // interface.write_data(0x01u8)?; //GPIO[0] out 1 --- LCD display on/off control PIN
-        Self::send_command(&mut writer, &mut delay, &commands::SetDisplayOn)?;
-        // commands::set_pwm_conf::SetPwmConf {
-        //     pwm_frequency: 6,
-        //     pwm_duty_cycle: 0xf0,
-        //     pwm_control: commands::ControlledBy::Host,
-        //     pwm_enable: true,
-        //     dbc_manual_brightness: 0xf0,
-        //     dbc_minimum_brightness: 0,
-        //     brightness_prescaler: commands::set_pwm_conf::BrightnessPrescaler::Off,
-        // }
-        // .send(&mut interface)?;
-        // commands::set_dbc_conf::SetDbcConf {
-        //     manual_brightness_enable: false,
-        //     transition_effect_enable: false,
-        //     energy_saving: commands::set_dbc_conf::EnergySaving::AgressiveMode,
-        //     master_enable: true,
-        // }
-        // .send(&mut interface)?;
-
-        Ok(Self { interface, lcd, delay })
+        send_command(&mut writer, delay, &commands::SetDisplayOn)?;
+        Ok(())
+    }
+
+    /// Sends any command with its parameters, e.g. `&commands::EnterSleepMode`
+    ///
+    /// The driver doesn't track state changed this way, a command that changes the panel setup
+    /// or the pixel format is overridden by `hard_reset` and may break the fills.
+    pub fn send<Command, const N: usize>(&mut self, command: &Command) -> Result<(), Error<Interface::Error>>
+    where
+        Command: CommandCode + CommandData<N>,
+    {
+        send_command(&mut self.interface.write().map_err(Error::Interface)?, &mut self.delay, command).map_err(Error::Interface)
+    }
+
+    /// Sets the thresholds of the dynamic backlight control's power saving levels
+    pub fn set_dbc_th(&mut self, th: &SetDbcTh) -> Result<(), Error<Interface::Error>> {
+        self.send(th)
+    }
+
+    /// Sets the raster operation combining the LCD generators on a GPIO controlled by the LCDC
+    pub fn set_gpio_rop<Rop: GpioRop>(&mut self, rop: &Rop) -> Result<(), Error<Interface::Error>> {
+        self.send(rop)
+    }
+
+    /// Configures one of the LCD signal generators
+    pub fn set_lcd_gen<Gen: LcdGen>(&mut self, generator: &Gen) -> Result<(), Error<Interface::Error>> {
+        self.send(generator)
+    }
+
+    /// Enables the post processor with the given settings, `SetPostProc::NEUTRAL` has the values leaving the image unchanged
+    pub fn set_picture(&mut self, contrast: u8, brightness: u8, saturation: u8) -> Result<(), Error<Interface::Error>> {
+        self.send(&SetPostProc {
+            contrast,
+            brightness,
+            saturation,
+            enable: true,
+        })
+    }
+
+    /// Passes the image to the panel unchanged
+    pub fn disable_post_processing(&mut self) -> Result<(), Error<Interface::Error>> {
+        self.send(&SetPostProc::NEUTRAL)
+    }
+
+    /// Splits the screen into `top_fixed` lines, `scroll_height` vertically scrolling lines and `bottom_fixed` lines
+    ///
+    /// The three must add up to the screen height. The scroll position is reset to the top.
+    pub fn set_scroll_region(&mut self, top_fixed: u16, scroll_height: u16, bottom_fixed: u16) -> Result<(), Error<Interface::Error>> {
+        self.check_not_double_buffered()?;
+        let region = ScrollRegion::new(top_fixed, scroll_height, bottom_fixed, Lcd::HEIGHT)?;
+        let mut writer = self.interface.write().map_err(Error::Interface)?;
+        send_command(&mut writer, &mut self.delay, &region.area()).map_err(Error::Interface)?;
+        send_command(&mut writer, &mut self.delay, &region.start(0)).map_err(Error::Interface)?;
+        self.scroll = region;
+        self.scroll_offset = 0;
+        Ok(())
+    }
+
+    /// Shows frame memory line `line` of the scrolling area at its top
+    pub fn scroll_to(&mut self, line: u16) -> Result<(), Error<Interface::Error>> {
+        self.check_not_double_buffered()?;
+        if line >= self.scroll.height {
+            return Err(ScrollError::LineOutOfRange.into());
+        }
+        self.send(&self.scroll.start(line))?;
+        self.scroll_offset = line;
+        Ok(())
+    }
+
+    /// Scrolls the content of the scrolling area up by `lines` lines, or down if negative, wrapping around
+    pub fn scroll_by(&mut self, lines: i16) -> Result<(), Error<Interface::Error>> {
+        self.check_not_double_buffered()?;
+        let offset = self.scroll.offset_by(self.scroll_offset, lines);
+        self.send(&self.scroll.start(offset))?;
+        self.scroll_offset = offset;
+        Ok(())
+    }
+
+    fn check_not_double_buffered(&self) -> Result<(), ScrollError> {
+        match self.back_buffer {
+            Some(_) => Err(ScrollError::DoubleBuffering),
+            None => Ok(()),
+        }
+    }
+
+    pub fn scroll_region(&self) -> ScrollRegion {
+        self.scroll
+    }
+
+    /// Frame memory row currently shown on screen row `y`, draw there to address the screen rather than the memory
+    pub fn scrolled_row(&self, y: u16) -> u16 {
+        self.scroll.map_row(self.scroll_offset, y)
+    }
+
+    /// Splits the frame buffer into two screen sized frames, drawing goes to the hidden one until `flip`
+    ///
+    /// The frames are switched through the scroll start address, so the whole screen is made a single scrolling area.
+    /// The frame buffer keeps 24 bits per pixel whatever the pixel format, so two frames fit only on small panels like 480x272.
+    /// The hidden frame lies below the visible one in the frame buffer, so the image can't be rotated meanwhile.
+    pub fn enable_double_buffer(&mut self) -> Result<(), Error<Interface::Error>> {
+        if 2 * 3 * u32::from(Lcd::WIDTH) * u32::from(Lcd::HEIGHT) > FRAME_BUFFER_BYTES {
+            return Err(Error::FrameBufferTooSmall);
+        }
+        if self.orientation.rotation != Rotation::Deg0 {
+            return Err(Error::UnsupportedOrientation);
+        }
+        if self.back_buffer.is_none() {
+            self.set_scroll_region(0, Lcd::HEIGHT, 0)?;
+            self.back_buffer = Some(Lcd::HEIGHT);
+        }
+        Ok(())
+    }
+
+    /// Goes back to drawing straight to the screen, which shows the first frame from now on
+    pub fn disable_double_buffer(&mut self) -> Result<(), Error<Interface::Error>> {
+        if self.back_buffer.take().is_some() {
+            self.send(&commands::SetScrollStart { source_line: 0 })?;
+        }
+        Ok(())
+    }
+
+    /// Shows the frame drawn since the last flip and hides the other one for drawing
+    ///
+    /// Does nothing unless double buffering is enabled.
+    pub fn flip(&mut self) -> Result<(), Error<Interface::Error>> {
+        if let Some(back) = self.back_buffer {
+            self.send(&commands::SetScrollStart { source_line: back })?;
+            self.back_buffer = Some(Lcd::HEIGHT - back);
+        }
+        Ok(())
+    }
+
+    /// Rotates and mirrors the image, `width`, `height` and the drawing coordinates follow
+    ///
+    /// Already drawn content isn't redrawn, it appears turned.
+    pub fn set_orientation(&mut self, rotation: Rotation, mirror: bool) -> Result<(), Error<Interface::Error>> {
+        if self.back_buffer.is_some() && rotation != Rotation::Deg0 {
+            return Err(Error::UnsupportedOrientation);
+        }
+        let orientation = Orientation::new(rotation, mirror);
+        self.send(&orientation.address_mode())?;
+        self.orientation = orientation;
+        Ok(())
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Configures the PWM output, usually driving the backlight
+    pub fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Error<Interface::Error>> {
+        self.send(&backlight.pwm_conf(&self.dbc))?;
+        self.backlight = backlight;
+        Ok(())
+    }
+
+    /// Backlight PWM settings last sent to the controller
+    pub fn backlight(&self) -> Backlight {
+        self.backlight
+    }
+
+    /// Sets the PWM duty cycle, 0 is off and 255 fully on
+    pub fn set_brightness(&mut self, brightness: u8) -> Result<(), Error<Interface::Error>> {
+        self.set_backlight(Backlight { brightness, ..self.backlight })
+    }
+
+    pub fn backlight_on(&mut self) -> Result<(), Error<Interface::Error>> {
+        self.set_backlight(Backlight {
+            enabled: true,
+            ..self.backlight
+        })
+    }
+
+    pub fn backlight_off(&mut self) -> Result<(), Error<Interface::Error>> {
+        self.set_backlight(Backlight {
+            enabled: false,
+            ..self.backlight
+        })
+    }
+
+    /// Sets the PWM signal frequency closest to `frequency` Hz the system clock allows and returns it
+    pub fn set_backlight_frequency(&mut self, frequency: u32) -> Result<u32, Error<Interface::Error>> {
+        let backlight = Backlight {
+            pwm_frequency: Backlight::pwm_frequency_for(&self.clocks, frequency),
+            ..self.backlight
+        };
+        self.set_backlight(backlight)?;
+        Ok(backlight.output_frequency(&self.clocks))
+    }
+
+    /// Configures dynamic backlight control, with thresholds derived from the screen size
+    ///
+    /// DBC dims the backlight PWM output, which has to be on, see `backlight_on`.
+    pub fn set_dbc(&mut self, dbc: Dbc) -> Result<(), Error<Interface::Error>> {
+        let mut writer = self.interface.write().map_err(Error::Interface)?;
+        send_command(&mut writer, &mut self.delay, &self.backlight.pwm_conf(&dbc)).map_err(Error::Interface)?;
+        send_command(&mut writer, &mut self.delay, &Dbc::thresholds(Lcd::WIDTH, Lcd::HEIGHT)).map_err(Error::Interface)?;
+        send_command(&mut writer, &mut self.delay, &dbc.dbc_conf()).map_err(Error::Interface)?;
+        self.dbc = dbc;
+        Ok(())
+    }
+
+    /// Dynamic backlight control settings last sent to the controller
+    pub fn dbc(&self) -> Dbc {
+        self.dbc
+    }
+
+    /// Sets who drives `pin` and whether it's an input or an output
+    pub fn configure_gpio(&mut self, pin: Gpio, control: gpio::ControlledBy, direction: gpio::Direction) -> Result<(), Error<Interface::Error>> {
+        let mut gpio = self.gpio;
+        gpio.configure(pin, control, direction);
+        self.send(&gpio.conf())?;
+        self.gpio = gpio;
+        Ok(())
+    }
+
+    /// Lets GPIO0 switch the panel power with `EnterSleepMode` and `ExitSleepMode` or makes it a normal GPIO
+    pub fn set_gpio0_power_control(&mut self, power_control: gpio::PowerControl) -> Result<(), Error<Interface::Error>> {
+        let mut gpio = self.gpio;
+        gpio.set_power_control(power_control);
+        self.send(&gpio.conf())?;
+        self.gpio = gpio;
+        Ok(())
+    }
+
+    /// Drives a host controlled output `pin` high or low
+    pub fn set_gpio(&mut self, pin: Gpio, high: bool) -> Result<(), Error<Interface::Error>> {
+        let mut gpio = self.gpio;
+        gpio.set_level(pin, high);
+        self.send(&gpio.value())?;
+        self.gpio = gpio;
+        Ok(())
+    }
+
+    /// `pin` as an `embedded_hal` pin for the lifetime of the borrow
+    pub fn gpio(&mut self, pin: Gpio) -> GpioPin<'_, Lcd, Delay, Interface, Format> {
+        GpioPin::new(self, pin)
+    }
+
+    /// Turns on the tearing effect output (TE), in `TearingMode::VHBlank` it also pulses when the display reaches `scanline`
+    pub fn enable_tearing_effect(&mut self, mode: TearingMode, scanline: u16) -> Result<(), Error<Interface::Error>> {
+        let mut writer = self.interface.write().map_err(Error::Interface)?;
+        send_command(&mut writer, &mut self.delay, &commands::SetTearScanline { scanline }).map_err(Error::Interface)?;
+        send_command(&mut writer, &mut self.delay, &commands::SetTearOn { mode }).map_err(Error::Interface)
+    }
+
+    pub fn disable_tearing_effect(&mut self) -> Result<(), Error<Interface::Error>> {
+        self.send(&commands::SetTearOff)
+    }
+
+    /// Waits for the start of the next vertical blanking, signalled by the TE output connected to `te`
+    ///
+    /// The tearing effect output has to be on, see `enable_tearing_effect`, otherwise this fails with
+    /// `Error::VblankTimeout` after two frames. A failing read of `te` is reported as `Error::Interface`.
+    pub fn wait_for_vblank_pin<TE>(&mut self, te: &TE) -> Result<(), Error<TE::Error>>
+    where
+        TE: InputPin,
+    {
+        self.check_awake()?;
+        let timeout_us = self.vblank_timeout_us();
+        let mut waited = 0;
+        // the rest of a pulse already going on is skipped, the next rising edge starts the blanking
+        for high in [true, false] {
+            while te.is_high().map_err(Error::Interface)? == high {
+                self.vblank_poll_delay(&mut waited, timeout_us)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Longest wait for the start of a vertical blanking, two frames
+    fn vblank_timeout_us(&self) -> u32 {
+        2 * (1_000_000_000 / self.clocks.refresh_rate_millihertz(&self.config).max(1))
+    }
+
+    /// Waits between two samples while waiting for the vertical blanking, until `timeout_us` is used up
+    fn vblank_poll_delay<E>(&mut self, waited: &mut u32, timeout_us: u32) -> Result<(), Error<E>> {
+        if *waited >= timeout_us {
+            return Err(Error::VblankTimeout);
+        }
+        self.delay.delay_us(VBLANK_POLL_US);
+        *waited += u32::from(VBLANK_POLL_US);
+        Ok(())
+    }
+
+    /// Moves the controller to `state`, waiting as long as the datasheet requires between the steps
+    ///
+    /// Waking up from `PowerState::DeepSleep` restarts the PLL. Drawing fails with `Error::Asleep` while sleeping.
+    pub fn set_power_state(&mut self, state: PowerState) -> Result<(), Error<Interface::Error>> {
+        if state == self.power {
+            return Ok(());
+        }
+        self.leave_power_state()?;
+        self.power = PowerState::Normal;
+        self.enter_power_state(state)?;
+        self.power = state;
+        Ok(())
+    }
+
+    pub fn power_state(&self) -> PowerState {
+        self.power
+    }
+
+    fn leave_power_state(&mut self) -> Result<(), Error<Interface::Error>> {
+        match self.power {
+            PowerState::Normal => Ok(()),
+            PowerState::DisplayOff => self.send(&commands::SetDisplayOn),
+            PowerState::Idle => self.send(&commands::ExitIdleMode),
+            PowerState::Sleep => self.send_and_wait(&commands::ExitSleepMode, SLEEP_WAIT_US),
+            PowerState::DeepSleep => {
+                // the first commands only wake the controller up, with the PLL stopped
+                self.send(&commands::Nop)?;
+                self.send(&commands::Nop)?;
+                Self::start_pll(&mut self.interface, &mut self.delay, &self.clocks).map_err(Error::Interface)?;
+                self.interface.wait_pll_lock(&mut self.delay, &self.clocks)?;
+                self.send(&commands::SetPll {
+                    system_clock_source: commands::set_pll::SystemClockSource::PllOutput,
+                    enable_pll: true,
+                })?;
+                self.send_and_wait(&commands::ExitSleepMode, SLEEP_WAIT_US)
+            }
+        }
+    }
+
+    fn enter_power_state(&mut self, state: PowerState) -> Result<(), Error<Interface::Error>> {
+        match state {
+            PowerState::Normal => Ok(()),
+            PowerState::DisplayOff => self.send(&commands::SetDisplayOff),
+            PowerState::Idle => self.send(&commands::EnterIdleMode),
+            PowerState::Sleep => self.send_and_wait(&commands::EnterSleepMode, SLEEP_WAIT_US),
+            PowerState::DeepSleep => {
+                self.send_and_wait(&commands::EnterSleepMode, SLEEP_WAIT_US)?;
+                // the PLL has to be off before the controller goes into deep sleep
+                self.send(&commands::SetPll {
+                    system_clock_source: commands::set_pll::SystemClockSource::ReferenceClock,
+                    enable_pll: false,
+                })?;
+                self.send(&commands::SetDeepSleep)
+            }
+        }
+    }
+
+    fn send_and_wait<Command, const N: usize>(&mut self, command: &Command, us: u32) -> Result<(), Error<Interface::Error>>
+    where
+        Command: CommandCode + CommandData<N>,
+    {
+        self.send(command)?;
+        delay_us(&mut self.delay, us);
+        Ok(())
+    }
+
+    fn check_awake<E>(&self) -> Result<(), Error<E>> {
+        if self.power.is_asleep() {
+            return Err(Error::Asleep);
+        }
+        Ok(())
+    }
+
+    /// Shows only frame memory rows `rows` of the panel, in its native orientation, the rest of the screen stays dark
+    pub fn set_partial_region<Y>(&mut self, rows: Y) -> Result<(), Error<Interface::Error>>
+    where
+        Y: RangeBounds<u16>,
+    {
+        let screen = Bounds {
+            x_start: 0,
+            x_end: Lcd::WIDTH - 1,
+            y_start: 0,
+            y_end: Lcd::HEIGHT - 1,
+        };
+        let bounds = Bounds::new_within(.., rows, &screen).map_err(|_| Error::InvalidPartialRegion)?;
+        if bounds.y_start > bounds.y_end {
+            return Err(Error::InvalidPartialRegion);
+        }
+        self.send(&commands::SetPartialArea {
+            start_row: bounds.y_start,
+            end_row: bounds.y_end,
+        })?;
+        self.send(&commands::EnterPartialMode)?;
+        self.partial = Some((bounds.y_start, bounds.y_end));
+        Ok(())
+    }
+
+    /// Leaves partial display mode, showing the whole screen again
+    pub fn exit_partial(&mut self) -> Result<(), Error<Interface::Error>> {
+        self.send(&commands::EnterNormalMode)?;
+        self.partial = None;
+        Ok(())
+    }
+
+    /// Rows shown in partial display mode, `None` in normal mode
+    pub fn partial_region(&self) -> Option<RangeInclusive<u16>> {
+        self.partial.map(|(start, end)| start..=end)
+    }
+
+    /// Selects one of the four gamma curves of the controller
+    pub fn set_gamma(&mut self, curve: GammaCurve) -> Result<(), Error<Interface::Error>> {
+        self.send(&commands::SetGammaCurve { curve })?;
+        self.gamma = curve;
+        Ok(())
+    }
+
+    /// Gamma curve last selected with `set_gamma`
+    pub fn gamma(&self) -> GammaCurve {
+        self.gamma
     }
 
     pub fn release(self) -> (Interface, Delay) {
         (self.interface, self.delay)
     }
+    /// Width of the image in its current orientation
     pub fn width(&self) -> u16 {
-        Lcd::WIDTH
+        if self.orientation.swaps_axes() {
+            Lcd::HEIGHT
+        } else {
+            Lcd::WIDTH
+        }
     }
+    /// Height of the image in its current orientation
     pub fn height(&self) -> u16 {
-        Lcd::HEIGHT
+        if self.orientation.swaps_axes() {
+            Lcd::WIDTH
+        } else {
+            Lcd::HEIGHT
+        }
     }
     fn display_size(&self) -> Bounds {
-        let width = Lcd::WIDTH;
-        let height = Lcd::HEIGHT;
+        let width = self.width();
+        let height = self.height();
         Bounds {
             x_start: 0,
             x_end: width - 1,
@@ -227,9 +818,17 @@ where
             y_start,
             y_end,
         } = bounds;
+        let row = self.back_buffer.unwrap_or(0);
         let mut writer = self.interface.write()?;
-        Self::send_command(&mut writer, &mut self.delay, &commands::SetColumnAddress { start: x_start, end: x_end })?;
-        Self::send_command(&mut writer, &mut self.delay, &commands::SetPageAddress { start: y_start, end: y_end })?;
+        send_command(&mut writer, &mut self.delay, &commands::SetColumnAddress { start: x_start, end: x_end })?;
+        send_command(
+            &mut writer,
+            &mut self.delay,
+            &commands::SetPageAddress {
+                start: y_start + row,
+                end: y_end + row,
+            },
+        )?;
         Ok(())
     }
 
@@ -243,90 +842,199 @@ where
         Ok(bounds)
     }
 
-    fn fill_area_bounds(&mut self, bounds: &Bounds, it: &mut dyn Iterator<Item = u16>) -> Result<(), Interface::Error> {
+    fn fill_area_bounds(&mut self, bounds: &Bounds, it: &mut dyn Iterator<Item = Format::Color>) -> Result<(), Interface::Error> {
         self.set_area_bounds(bounds)?;
         let mut writer = self.interface.write()?;
-        Self::send_command(&mut writer, &mut self.delay, &commands::WriteMemoryStart)?;
+        send_command(&mut writer, &mut self.delay, &commands::WriteMemoryStart)?;
         let mut data = writer.data()?;
-        for color in it.limit(bounds.area()) {
-            let mut c = data.set_value(color)?;
-            c.commit()?;
+        let mut it = it.limit(bounds.area());
+        loop {
+            let mut pixels = Format::Pixels::default();
+            let count = pixels.as_mut().iter_mut().zip(&mut it).map(|(pixel, color)| *pixel = color).count();
+            if count == 0 {
+                break;
+            }
+            for &word in &Format::pack(&pixels).as_ref()[..Format::words(count)] {
+                data.set_value(word)?.commit()?;
+            }
         }
         Ok(())
     }
 
-    pub fn fill_area_color<X, Y>(&mut self, x: X, y: Y, color: u16) -> Result<(), Interface::Error>
+    pub fn fill_area_color<X, Y>(&mut self, x: X, y: Y, color: Format::Color) -> Result<(), Error<Interface::Error>>
+    where
+        X: RangeBounds<u16>,
+        Y: RangeBounds<u16>,
+    {
+        self.check_awake()?;
+        self.write_area_color(x, y, color).map_err(Error::Interface)
+    }
+
+    fn write_area_color<X, Y>(&mut self, x: X, y: Y, color: Format::Color) -> Result<(), Interface::Error>
     where
         X: RangeBounds<u16>,
         Y: RangeBounds<u16>,
     {
         let bounds = self.set_area(x, y)?;
         let mut writer = self.interface.write()?;
-        Self::send_command(&mut writer, &mut self.delay, &commands::WriteMemoryStart)?;
+        send_command(&mut writer, &mut self.delay, &commands::WriteMemoryStart)?;
         let mut data = writer.data()?;
-        let mut comitter = data.set_value(color)?;
-        for _ in 0..bounds.area() {
-            comitter.commit()?;
+        let mut pixels = Format::Pixels::default();
+        pixels.as_mut().fill(color);
+        let words = Format::pack(&pixels);
+        if let [word] = *words.as_ref() {
+            // the bus keeps its value, strobing WR is enough to repeat it
+            let mut comitter = data.set_value(word)?;
+            for _ in 0..bounds.area() {
+                comitter.commit()?;
+            }
+        } else {
+            let group = pixels.as_ref().len() as u32;
+            for _ in 0..bounds.area() / group {
+                for &word in words.as_ref() {
+                    data.set_value(word)?.commit()?;
+                }
+            }
+            let remainder = (bounds.area() % group) as usize;
+            if remainder > 0 {
+                for &word in &words.as_ref()[..Format::words(remainder)] {
+                    data.set_value(word)?.commit()?;
+                }
+            }
         }
         Ok(())
     }
 
-    pub fn clear_screen(&mut self, color: u16) -> Result<(), Interface::Error> {
+    pub fn clear_screen(&mut self, color: Format::Color) -> Result<(), Error<Interface::Error>> {
         self.fill_area_color(.., .., color)?;
         Ok(())
     }
 }
 
-pub struct DisplayDataIter<'r, Interface>
+pub struct DisplayDataIter<'r, Interface, Format>
 where
     Interface: ReadWriteInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
 {
-    getter: ValueGetter<'r, <Interface as ReadWriteInterface>::Port, Interface::RD, Interface::Error>,
+    getter: ValueGetter<'r, <Interface as ReadWriteInterface>::Port, Interface::RD, Interface::CS, Interface::Error>,
+    /// Last group read from the controller, `next` is the first pixel not handed out yet
+    pixels: Format::Pixels,
+    next: usize,
     count: u32,
 }
 
-impl<'r, Interface> Iterator for DisplayDataIter<'r, Interface>
+impl<'r, Interface, Format> Iterator for DisplayDataIter<'r, Interface, Format>
 where
     Interface: ReadWriteInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
 {
-    type Item = Result<u16, Interface::Error>;
+    type Item = Result<Format::Color, Interface::Error>;
     fn next(&mut self) -> Option<Self::Item> {
         if self.count == 0 {
             return None;
         }
+        let group = self.pixels.as_ref().len();
+        if self.next == group {
+            let pixels = min(self.count as usize, group);
+            let mut words = Format::Words::default();
+            for word in &mut words.as_mut()[..Format::words(pixels)] {
+                match self.getter.get_value() {
+                    Ok(value) => *word = value,
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+            self.pixels = Format::unpack(&words);
+            self.next = 0;
+        }
         self.count -= 1;
-        Some(self.getter.get_value())
+        self.next += 1;
+        Some(Ok(self.pixels.as_ref()[self.next - 1]))
     }
 }
 
-impl<Lcd, Delay, Interface> Ssd1963<Lcd, Delay, Interface>
+impl<Lcd, Delay, Interface, Format> Ssd1963<Lcd, Delay, Interface, Format>
 where
     Lcd: Screen,
     Delay: embedded_hal::blocking::delay::DelayUs<u8>,
     Interface: ReadWriteInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
 {
-    fn read_area_bounds(&mut self, bounds: &Bounds) -> Result<DisplayDataIter<Interface>, Interface::Error> {
+    /// Sends a get_* command and decodes the controller's reply
+    pub fn query<Cmd>(&mut self) -> Result<Cmd::Response, Error<Interface::Error>>
+    where
+        Cmd: Query,
+    {
+        read_response::<Cmd, _, _>(&mut self.interface, &mut self.delay)
+    }
+
+    /// Line the controller is scanning out, counted from the start of the vertical sync
+    pub fn scanline(&mut self) -> Result<u16, Error<Interface::Error>> {
+        Ok(*self.query::<commands::GetScanline>()?.line)
+    }
+
+    /// Waits for the start of the next vertical blanking by polling `get_scanline`, for at most two frames
+    pub fn wait_for_vblank(&mut self) -> Result<(), Error<Interface::Error>> {
+        let first = self.config.vertical.vps;
+        let last = first + Lcd::HEIGHT;
+        let blanking = |line| line < first || line >= last;
+        self.check_awake()?;
+        let timeout_us = self.vblank_timeout_us();
+        let mut waited = 0;
+        for in_blanking in [true, false] {
+            while blanking(self.scanline()?) == in_blanking {
+                self.vblank_poll_delay(&mut waited, timeout_us)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the selected gamma curve back from the controller
+    pub fn read_gamma(&mut self) -> Result<GammaCurve, Error<Interface::Error>> {
+        let mode = self.query::<commands::GetDisplayMode>()?;
+        GammaCurve::from_index(*mode.gamma_curve).ok_or(Error::InvalidResponse)
+    }
+
+    /// Reads the level of `pin`, for outputs the level driven
+    pub fn read_gpio(&mut self, pin: Gpio) -> Result<bool, Error<Interface::Error>> {
+        let status = self.query::<commands::GetGpioStatus>()?;
+        Ok(gpio::level(&status, pin))
+    }
+
+    /// Reads the post processor settings back from the controller
+    pub fn read_picture(&mut self) -> Result<SetPostProc, Error<Interface::Error>> {
+        self.query::<commands::GetPostProc>()
+    }
+
+    /// Reads the backlight PWM settings back from the controller
+    pub fn read_backlight(&mut self) -> Result<Backlight, Error<Interface::Error>> {
+        self.query::<commands::GetPwmConf>().map(Backlight::from)
+    }
+
+    fn read_area_bounds(&mut self, bounds: &Bounds) -> Result<DisplayDataIter<Interface, Format>, Interface::Error> {
         self.set_area_bounds(&bounds)?;
-        Self::send_command(&mut self.interface.write()?, &mut self.delay, &commands::ReadMemoryStart)?;
+        send_command(&mut self.interface.write()?, &mut self.delay, &commands::ReadMemoryStart)?;
 
         let it = DisplayDataIter {
             getter: self.interface.read()?.into_data()?,
+            pixels: Format::Pixels::default(),
+            next: Format::Pixels::default().as_ref().len(),
             count: bounds.area(),
         };
         Ok(it)
     }
 
-    fn copy_area_bounds(&mut self, from: &Bounds, horiz_by: i16, vert_by: i16, buffer: &mut [u16]) -> Result<(), Interface::Error> {
-        fn copy<Lcd, Delay, Interface>(
-            me: &mut Ssd1963<Lcd, Delay, Interface>,
+    fn copy_area_bounds(&mut self, from: &Bounds, horiz_by: i16, vert_by: i16, buffer: &mut [Format::Color]) -> Result<(), Interface::Error> {
+        fn copy<Lcd, Delay, Interface, Format>(
+            me: &mut Ssd1963<Lcd, Delay, Interface, Format>,
             from: &Bounds,
             to: &Bounds,
-            buffer: &mut [u16],
+            buffer: &mut [Format::Color],
         ) -> Result<(), Interface::Error>
         where
             Lcd: Screen,
             Delay: embedded_hal::blocking::delay::DelayUs<u8>,
             Interface: ReadWriteInterface,
+            Format: PixelFormat<Word = PortWord<Interface>>,
         {
             let it = me.read_area_bounds(from)?;
             if let Some(err) = buffer.iter_mut().zip(it).find_map(|(dest, item)| match item {
@@ -386,53 +1094,48 @@ where
     }
 }
 
-impl<Lcd, Delay, Interface> Display for Ssd1963<Lcd, Delay, Interface>
+impl<Lcd, Delay, Interface, Format> Display for Ssd1963<Lcd, Delay, Interface, Format>
 where
     Lcd: Screen,
     Delay: embedded_hal::blocking::delay::DelayUs<u8>,
     Interface: ReadWriteInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
 {
     const WIDTH: u16 = Lcd::WIDTH;
     const HEIGHT: u16 = Lcd::HEIGHT;
 
-    type Color = u16;
-    type Error = Interface::Error;
+    type Color = Format::Color;
+    type Error = Error<Interface::Error>;
 
-    fn fill_area<X, Y>(&mut self, x: X, y: Y, it: &mut dyn Iterator<Item = u16>) -> Result<(), Self::Error>
+    fn fill_area<X, Y>(&mut self, x: X, y: Y, it: &mut dyn Iterator<Item = Format::Color>) -> Result<(), Self::Error>
     where
         X: RangeBounds<u16>,
         Y: RangeBounds<u16>,
     {
+        self.check_awake()?;
         self.fill_area_bounds(&Bounds::new_within(x, y, &self.display_size()).unwrap(), it)
+            .map_err(Error::Interface)
     }
 }
 
-impl<Lcd, Delay, Interface> CopyArea for Ssd1963<Lcd, Delay, Interface>
+impl<Lcd, Delay, Interface, Format> CopyArea for Ssd1963<Lcd, Delay, Interface, Format>
 where
     Lcd: Screen,
     Delay: embedded_hal::blocking::delay::DelayUs<u8>,
     Interface: ReadWriteInterface,
+    Format: PixelFormat<Word = PortWord<Interface>>,
 {
-    fn copy_area<X, Y>(&mut self, x: X, y: Y, horiz_by: i16, vert_by: i16, buffer: &mut [u16]) -> Result<(), Self::Error>
+    fn copy_area<X, Y>(&mut self, x: X, y: Y, horiz_by: i16, vert_by: i16, buffer: &mut [Format::Color]) -> Result<(), Self::Error>
     where
         X: RangeBounds<u16>,
         Y: RangeBounds<u16>,
     {
+        self.check_awake()?;
         self.copy_area_bounds(&Bounds::new_within(x, y, &self.display_size()).unwrap(), horiz_by, vert_by, buffer)
+            .map_err(Error::Interface)
     }
 }
 
-pub trait Screen {
-    const WIDTH: u16;
-    const HEIGHT: u16;
-}
-
-pub struct Lcd800x480;
-impl Screen for Lcd800x480 {
-    const WIDTH: u16 = 800;
-    const HEIGHT: u16 = 481;
-}
-
 struct Limit<I, N> {
     it: I,
     remaining: N,
@@ -492,3 +1195,432 @@ where
 //         self.read_area_bounds(&Bounds::new_within(x, y, &self.display_size()).unwrap())
 //     }
 // }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{self, Bus, Event, MockInput, Pin, PLL_LOCKED};
+    use commands::{GetPllStatus, SetPll, SetPllMn};
+
+    fn clocks() -> Clocks {
+        Clocks::new(DEFAULT_REFERENCE_CLOCK, DEFAULT_SYSTEM_CLOCK, Lcd480x272::PIXEL_CLOCK).unwrap()
+    }
+
+    #[test]
+    fn poll_pll_lock_until_locked() {
+        let bus = Bus::default();
+        bus.queue_reads(&[0, 0, PLL_LOCKED]);
+        assert_eq!(poll_pll_lock(&mut mock::interface(&bus), &mut bus.delay(), &clocks()), Ok(()));
+        assert_eq!(bus.codes(), [GetPllStatus::CODE; 3]);
+    }
+
+    #[test]
+    fn poll_pll_lock_timeout() {
+        let bus = Bus::default();
+        let clocks = Clocks {
+            lock_timeout_us: 100,
+            ..clocks()
+        };
+        assert_eq!(poll_pll_lock(&mut mock::interface(&bus), &mut bus.delay(), &clocks), Err(Error::PllLockTimeout));
+        assert_eq!(bus.codes(), [GetPllStatus::CODE; 11]);
+    }
+
+    #[test]
+    fn new_polls_read_write_interfaces() {
+        let bus = Bus::default();
+        bus.queue_reads(&[0, PLL_LOCKED]);
+        assert!(Ssd1963::new(Lcd480x272, mock::interface(&bus), bus.delay()).is_ok());
+        assert_eq!(
+            bus.codes()[..6],
+            [SetPll::CODE, SetPllMn::CODE, SetPll::CODE, GetPllStatus::CODE, GetPllStatus::CODE, SetPll::CODE]
+        );
+        assert_eq!(bus.commands()[5], (SetPll::CODE, vec![0b11]));
+    }
+
+    #[test]
+    fn new_waits_on_write_only_interfaces() {
+        let bus = Bus::default();
+        let interface = GpioWriteOnly16BitInterface::new(bus.port::<u16>(), bus.pin(Pin::Dc), bus.pin(Pin::Wr));
+        assert!(Ssd1963::new(Lcd480x272, interface, bus.delay()).is_ok());
+        assert_eq!(bus.codes()[..4], [SetPll::CODE, SetPllMn::CODE, SetPll::CODE, SetPll::CODE]);
+        assert!(bus.waits()[2] >= pll::PLL_LOCK_TIME_US);
+    }
+
+    /// RESET levels with the time waited after each change, up to the first command
+    fn reset_pulse(bus: &Bus) -> Vec<(bool, u32)> {
+        let mut pulse: Vec<(bool, u32)> = Vec::new();
+        for event in bus.events().into_iter().take_while(|event| !matches!(event, Event::Write(_))) {
+            match event {
+                Event::Pin(Pin::Rst, level) => pulse.push((level, 0)),
+                Event::Delay(us) => pulse.last_mut().unwrap().1 += us,
+                _ => {}
+            }
+        }
+        pulse
+    }
+
+    #[test]
+    fn wide_formats_on_8080_bus() {
+        let bus = Bus::default();
+        bus.queue_reads(&[PLL_LOCKED]);
+        let interface = GpioReadWrite8080Interface::new(bus.port::<u32>(), bus.pin(Pin::Dc), bus.pin(Pin::Wr), bus.pin(Pin::Rd));
+        let driver = Ssd1963::new(Lcd480x272, interface, bus.delay()).unwrap();
+        bus.clear();
+        let mut driver = driver.with_pixel_format::<pixel_format::B18>().map_err(|(_, err)| err).unwrap();
+        assert_eq!(bus.commands(), [(commands::SetPixelDataInterface::CODE, vec![0b100])]);
+        bus.clear();
+        driver.fill_area_color(0..1, 0..1, color::Rgb666 { r: 0x3F, g: 0, b: 0x01 }).unwrap();
+        assert_eq!(bus.commands().last().unwrap().1, [0x3F001]);
+    }
+
+    #[test]
+    fn scrolling() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        driver.set_scroll_region(10, 252, 10).unwrap();
+        assert_eq!(bus.commands(), [(0x33, vec![0, 10, 0, 252, 0, 10]), (0x37, vec![0, 10])]);
+        bus.clear();
+        driver.scroll_to(250).unwrap();
+        driver.scroll_by(5).unwrap();
+        driver.scroll_by(-4).unwrap();
+        assert_eq!(bus.commands(), [(0x37, vec![1, 4]), (0x37, vec![0, 13]), (0x37, vec![1, 5])]);
+        assert_eq!(driver.scroll_to(252), Err(Error::Scroll(ScrollError::LineOutOfRange)));
+        assert_eq!(driver.set_scroll_region(10, 252, 0), Err(Error::Scroll(ScrollError::InvalidRegion)));
+    }
+
+    #[test]
+    fn scrolling_refused_while_double_buffered() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        driver.enable_double_buffer().unwrap();
+        bus.clear();
+        assert_eq!(driver.set_scroll_region(0, 272, 0), Err(Error::Scroll(ScrollError::DoubleBuffering)));
+        assert_eq!(driver.scroll_to(1), Err(Error::Scroll(ScrollError::DoubleBuffering)));
+        assert_eq!(driver.scroll_by(1), Err(Error::Scroll(ScrollError::DoubleBuffering)));
+        assert_eq!(bus.commands(), []);
+    }
+
+    #[test]
+    fn orientation_swaps_drawing_axes() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        driver.set_orientation(Rotation::Deg90, false).unwrap();
+        assert_eq!(bus.commands(), [(0x36, vec![0b0110_0000])]);
+        assert_eq!((driver.width(), driver.height()), (272, 480));
+        bus.clear();
+        driver.fill_area_color(.., .., Default::default()).unwrap();
+        assert_eq!(bus.commands()[..2], [(0x2A, vec![0, 0, 0x01, 0x0F]), (0x2B, vec![0, 0, 0x01, 0xDF])]);
+        bus.clear();
+        driver.set_orientation(Rotation::Deg180, true).unwrap();
+        assert_eq!(bus.commands(), [(0x36, vec![0b1000_0000])]);
+        assert_eq!((driver.width(), driver.height()), (480, 272));
+        assert_eq!(driver.orientation(), Orientation::new(Rotation::Deg180, true));
+    }
+
+    /// Payloads of the `set_pwm_conf` commands sent so far
+    fn pwm_confs(bus: &Bus) -> Vec<Vec<u32>> {
+        bus.commands().into_iter().filter(|(code, _)| *code == 0xBE).map(|(_, data)| data).collect()
+    }
+
+    #[test]
+    fn backlight_settings() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        driver
+            .set_dbc(Dbc {
+                minimum_brightness: 0x20,
+                brightness_prescaler: backlight::BrightnessPrescaler::B4,
+                ..Dbc::default()
+            })
+            .unwrap();
+        bus.clear();
+        driver.set_brightness(0x80).unwrap();
+        driver.backlight_on().unwrap();
+        assert_eq!(driver.set_backlight_frequency(300), Ok(305));
+        driver.backlight_off().unwrap();
+        assert_eq!(
+            pwm_confs(&bus),
+            [
+                [0, 0x80, 0b0000, 0, 0x20, 0b0100],
+                [0, 0x80, 0b0001, 0, 0x20, 0b0100],
+                [4, 0x80, 0b0001, 0, 0x20, 0b0100],
+                [4, 0x80, 0b0000, 0, 0x20, 0b0100],
+            ]
+        );
+        let backlight = Backlight {
+            pwm_frequency: 4,
+            brightness: 0x80,
+            enabled: false,
+        };
+        assert_eq!(driver.backlight(), backlight);
+    }
+
+    #[test]
+    fn set_dbc_sequence() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        let dbc = Dbc {
+            energy_saving: backlight::EnergySaving::NormalMode,
+            transition_effect: true,
+            ..Dbc::default()
+        };
+        driver.set_dbc(dbc).unwrap();
+        assert_eq!(
+            bus.commands(),
+            [
+                (0xBE, vec![0, 0, 0b1000, 0, 0, 0]),
+                (0xD4, vec![0, 0x09, 0x90, 0, 0x17, 0xE8, 0, 0x39, 0x60]),
+                (0xD0, vec![0b0010_1001]),
+            ]
+        );
+        assert_eq!(driver.dbc(), dbc);
+    }
+
+    #[test]
+    fn read_backlight() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        bus.queue_reads(&[6, 0xF0, 0b1001, 0x80, 0x10, 0b1111, 1]);
+        let backlight = Backlight {
+            pwm_frequency: 6,
+            brightness: 0xF0,
+            enabled: true,
+        };
+        assert_eq!(driver.read_backlight(), Ok(backlight));
+        assert_eq!(bus.codes(), [commands::GetPwmConf::CODE]);
+    }
+
+    #[test]
+    fn double_buffer_needs_two_24bpp_frames() {
+        let bus = Bus::default();
+        bus.queue_reads(&[PLL_LOCKED]);
+        let mut driver = Ssd1963::new(Lcd800x480, mock::interface(&bus), bus.delay()).unwrap();
+        assert_eq!(driver.enable_double_buffer(), Err(Error::FrameBufferTooSmall));
+        assert_eq!(mock::driver(&bus).enable_double_buffer(), Ok(()));
+    }
+
+    /// First row of the page address set by the last fill
+    fn page_start(driver: &mut mock::MockDriver, bus: &Bus) -> u32 {
+        bus.clear();
+        driver.fill_area_color(0..1, 0..1, Default::default()).unwrap();
+        let (_, page) = bus.commands().into_iter().find(|(code, _)| *code == commands::SetPageAddress::CODE).unwrap();
+        page[0] << 8 | page[1]
+    }
+
+    #[test]
+    fn flip_alternates_frames() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        assert_eq!(page_start(&mut driver, &bus), 0);
+        driver.enable_double_buffer().unwrap();
+        assert_eq!(page_start(&mut driver, &bus), 272);
+        bus.clear();
+        driver.flip().unwrap();
+        assert_eq!(bus.commands(), [(commands::SetScrollStart::CODE, vec![1, 0x10])]);
+        assert_eq!(page_start(&mut driver, &bus), 0);
+        bus.clear();
+        driver.flip().unwrap();
+        assert_eq!(bus.commands(), [(commands::SetScrollStart::CODE, vec![0, 0])]);
+        assert_eq!(page_start(&mut driver, &bus), 272);
+    }
+
+    #[test]
+    fn disable_double_buffer_shows_first_frame() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        driver.enable_double_buffer().unwrap();
+        driver.flip().unwrap();
+        bus.clear();
+        driver.disable_double_buffer().unwrap();
+        assert_eq!(bus.commands(), [(commands::SetScrollStart::CODE, vec![0, 0])]);
+        assert_eq!(page_start(&mut driver, &bus), 0);
+        bus.clear();
+        driver.flip().unwrap();
+        driver.disable_double_buffer().unwrap();
+        assert_eq!(bus.commands(), []);
+    }
+
+    #[test]
+    fn picture_settings() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        driver.set_picture(0x30, 0x90, 0x50).unwrap();
+        driver.disable_post_processing().unwrap();
+        assert_eq!(
+            bus.commands(),
+            [(SetPostProc::CODE, vec![0x30, 0x90, 0x50, 1]), (SetPostProc::CODE, vec![0x40, 0x80, 0x40, 0])]
+        );
+        bus.clear();
+        bus.queue_reads(&[0x30, 0x90, 0x50, 1]);
+        let picture = SetPostProc {
+            contrast: 0x30,
+            brightness: 0x90,
+            saturation: 0x50,
+            enable: true,
+        };
+        assert_eq!(driver.read_picture(), Ok(picture));
+        assert_eq!(bus.codes(), [commands::GetPostProc::CODE]);
+    }
+
+    fn delays(bus: &Bus) -> usize {
+        let poll = Event::Delay(VBLANK_POLL_US.into());
+        bus.events().iter().filter(|&&event| event == poll).count()
+    }
+
+    #[test]
+    fn wait_for_vblank_pin_skips_current_pulse() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        let te = MockInput::new(&[true, true, false, false, true]);
+        assert_eq!(driver.wait_for_vblank_pin(&te), Ok(()));
+        assert_eq!(delays(&bus), 3);
+    }
+
+    #[test]
+    fn wait_for_vblank_pin_timeout() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        assert_eq!(driver.wait_for_vblank_pin(&MockInput::new(&[false])), Err(Error::VblankTimeout));
+        let timeout_us = 2 * (1_000_000_000 / driver.clocks.refresh_rate_millihertz(&driver.config));
+        assert_eq!(delays(&bus), timeout_us.div_ceil(VBLANK_POLL_US.into()) as usize);
+        driver.set_power_state(PowerState::Sleep).unwrap();
+        assert_eq!(driver.wait_for_vblank_pin(&MockInput::new(&[false, true])), Err(Error::Asleep));
+    }
+
+    #[test]
+    fn wait_for_vblank_polls_scanline() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        let vps = driver.config.vertical.vps;
+        let blanking = vps + Lcd480x272::HEIGHT;
+        let (visible, blanking) = ([0, u32::from(vps)], [u32::from(blanking >> 8), u32::from(blanking & 0xFF)]);
+        bus.queue_reads(&[blanking, visible, visible, blanking].concat());
+        assert_eq!(driver.wait_for_vblank(), Ok(()));
+        assert_eq!(bus.codes(), [commands::GetScanline::CODE; 4]);
+        assert_eq!(delays(&bus), 2);
+        bus.clear();
+        assert_eq!(driver.wait_for_vblank(), Err(Error::VblankTimeout));
+    }
+
+    #[test]
+    fn sleep_round_trip() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        driver.set_power_state(PowerState::Sleep).unwrap();
+        assert_eq!(bus.codes(), [commands::EnterSleepMode::CODE]);
+        bus.clear();
+        driver.set_power_state(PowerState::Normal).unwrap();
+        assert_eq!(bus.codes(), [commands::ExitSleepMode::CODE]);
+        assert!(bus.waits()[0] >= SLEEP_WAIT_US);
+        assert_eq!(driver.power_state(), PowerState::Normal);
+    }
+
+    #[test]
+    fn deep_sleep_round_trip() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        driver.set_power_state(PowerState::DeepSleep).unwrap();
+        assert_eq!(
+            bus.commands(),
+            [(commands::EnterSleepMode::CODE, vec![]), (SetPll::CODE, vec![0]), (commands::SetDeepSleep::CODE, vec![])]
+        );
+        assert!(bus.waits()[0] >= SLEEP_WAIT_US);
+        bus.clear();
+        bus.queue_reads(&[0, PLL_LOCKED]);
+        driver.set_power_state(PowerState::Normal).unwrap();
+        assert_eq!(
+            bus.codes(),
+            [
+                commands::Nop::CODE,
+                commands::Nop::CODE,
+                SetPll::CODE,
+                SetPllMn::CODE,
+                SetPll::CODE,
+                GetPllStatus::CODE,
+                GetPllStatus::CODE,
+                SetPll::CODE,
+                commands::ExitSleepMode::CODE,
+            ]
+        );
+        assert_eq!(bus.commands()[7], (SetPll::CODE, vec![0b11]));
+        assert!(bus.waits()[8] >= SLEEP_WAIT_US);
+    }
+
+    #[test]
+    fn partial_region_rejects_empty_rows() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        assert_eq!(driver.set_partial_region(..0), Err(Error::InvalidPartialRegion));
+        assert_eq!(driver.set_partial_region(0..0), Err(Error::InvalidPartialRegion));
+        assert_eq!(driver.set_partial_region(5..5), Err(Error::InvalidPartialRegion));
+        assert_eq!(bus.codes(), []);
+        assert_eq!(driver.set_partial_region(5..10), Ok(()));
+        assert_eq!(driver.partial_region(), Some(5..=9));
+    }
+
+    #[test]
+    fn send_writes_code_and_payload() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        driver.send(&commands::SetTearScanline { scanline: 0x123 }).unwrap();
+        driver.send(&commands::EnterSleepMode).unwrap();
+        assert_eq!(bus.commands(), [(0x44, vec![0x01, 0x23]), (0x10, vec![])]);
+    }
+
+    #[test]
+    fn query_round_trips_response() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        let lcd_mode = Lcd480x272::CONFIG.lcd_mode(Lcd480x272::WIDTH, Lcd480x272::HEIGHT);
+        let hori_period = Lcd480x272::CONFIG.hori_period();
+        bus.queue_reads(&lcd_mode.data().map(u32::from));
+        bus.queue_reads(&hori_period.data().map(u32::from));
+        assert_eq!(driver.query::<commands::GetLcdMode>(), Ok(lcd_mode));
+        assert_eq!(driver.query::<commands::GetHoriPeriod>(), Ok(hori_period));
+        assert_eq!(bus.codes(), [commands::GetLcdMode::CODE, commands::GetHoriPeriod::CODE]);
+    }
+
+    #[test]
+    fn gamma_curve() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        assert_eq!(driver.gamma(), GammaCurve::Curve0);
+        driver.set_gamma(GammaCurve::Curve2).unwrap();
+        driver.set_gamma(GammaCurve::Curve3).unwrap();
+        assert_eq!(bus.commands(), [(0x26, vec![0b0100]), (0x26, vec![0b1000])]);
+        assert_eq!(driver.gamma(), GammaCurve::Curve3);
+        bus.clear();
+        // the reply holds the index of the curve in its low bits
+        bus.queue_reads(&[0b1010_0010, 0b0000_0100, 0b0000_0111]);
+        assert_eq!(driver.read_gamma(), Ok(GammaCurve::Curve2));
+        assert_eq!(driver.read_gamma(), Err(Error::InvalidResponse));
+        assert_eq!(driver.read_gamma(), Err(Error::InvalidResponse));
+        assert_eq!(bus.codes(), [commands::GetDisplayMode::CODE; 3]);
+    }
+
+    #[test]
+    fn hard_reset_pulses_reset_pin() {
+        let bus = Bus::default();
+        let interface = GpioWriteOnly16BitInterface::new(bus.port::<u16>(), bus.pin(Pin::Dc), bus.pin(Pin::Wr))
+            .with_reset(bus.pin(Pin::Rst))
+            .unwrap();
+        let mut driver = Ssd1963::new(Lcd480x272, interface, bus.delay()).unwrap();
+        assert_eq!(reset_pulse(&bus), [(true, 0), (false, RESET_PULSE_US), (true, RESET_RECOVERY_US)]);
+        bus.clear();
+        driver.hard_reset().unwrap();
+        assert_eq!(reset_pulse(&bus), [(false, RESET_PULSE_US), (true, RESET_RECOVERY_US)]);
+        assert_eq!(bus.codes()[..2], [SetPll::CODE, SetPllMn::CODE]);
+    }
+
+    #[test]
+    fn hard_reset_stops_pll_before_changing_mn() {
+        let bus = Bus::default();
+        let mut driver = mock::driver(&bus);
+        bus.queue_reads(&[PLL_LOCKED]);
+        driver.hard_reset().unwrap();
+        let commands = bus.commands();
+        assert_eq!(commands[0], (SetPll::CODE, vec![0]));
+        assert_eq!(commands[1].0, SetPllMn::CODE);
+        assert_eq!(commands[2], (SetPll::CODE, vec![0b01]));
+    }
+}
@@ -0,0 +1,263 @@
+//! Test doubles recording the traffic on the bus, the pins and the delay
+
+use crate::interface::{BusWord, ReadWritePort, WritePort};
+use crate::{GpioReadWrite16BitInterface, Lcd480x272, NoPin, Ssd1963};
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// `get_pll_status` reply of a locked PLL
+pub const PLL_LOCKED: u32 = 0b100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pin {
+    Dc,
+    Wr,
+    Rd,
+    Cs,
+    Rst,
+    Rw,
+    E,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    Pin(Pin, bool),
+    Write(u32),
+    Read(u32),
+    DirRead,
+    DirWrite,
+    Delay(u32),
+}
+
+#[derive(Default)]
+struct State {
+    events: Vec<Event>,
+    reads: VecDeque<u32>,
+}
+
+/// Log shared by all the doubles handed out
+#[derive(Clone, Default)]
+pub struct Bus(Rc<RefCell<State>>);
+
+/// A command as the controller sees it, with the data words strobed after it and the time waited until the next one
+struct Transfer {
+    code: u8,
+    data: Vec<u32>,
+    wait: u32,
+}
+
+impl Bus {
+    pub fn pin(&self, pin: Pin) -> MockPin {
+        MockPin { bus: self.clone(), pin }
+    }
+
+    pub fn port<W>(&self) -> MockPort<W> {
+        MockPort {
+            bus: self.clone(),
+            word: PhantomData,
+        }
+    }
+
+    pub fn delay(&self) -> MockDelay {
+        MockDelay { bus: self.clone() }
+    }
+
+    /// Values returned by the next reads of the port, reads return 0 once they run out
+    pub fn queue_reads(&self, values: &[u32]) {
+        self.0.borrow_mut().reads.extend(values);
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        self.0.borrow().events.clone()
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.0.borrow_mut();
+        state.events.clear();
+        state.reads.clear();
+    }
+
+    fn log(&self, event: Event) {
+        self.0.borrow_mut().events.push(event);
+    }
+
+    /// Decodes the log the way the controller does, a word is taken on the rising edge of WR (8080)
+    /// or the falling edge of E (6800)
+    fn decode(&self) -> Vec<Transfer> {
+        let mut transfers: Vec<Transfer> = Vec::new();
+        let (mut dc, mut wr, mut value) = (true, true, 0);
+        for event in self.events() {
+            let strobe = match event {
+                Event::Pin(Pin::Wr, level) => Some(level),
+                Event::Pin(Pin::E, level) => Some(!level),
+                _ => None,
+            };
+            match (event, strobe) {
+                (Event::Pin(Pin::Dc, level), _) => dc = level,
+                (_, Some(level)) => {
+                    if level && !wr {
+                        if !dc {
+                            transfers.push(Transfer {
+                                code: value as u8,
+                                data: Vec::new(),
+                                wait: 0,
+                            });
+                        } else if let Some(transfer) = transfers.last_mut() {
+                            transfer.data.push(value);
+                        }
+                    }
+                    wr = level;
+                }
+                (Event::Write(word), _) => value = word,
+                (Event::Delay(us), _) => {
+                    if let Some(transfer) = transfers.last_mut() {
+                        transfer.wait += us;
+                    }
+                }
+                _ => {}
+            }
+        }
+        transfers
+    }
+
+    /// Commands written so far with their data words
+    pub fn commands(&self) -> Vec<(u8, Vec<u32>)> {
+        self.decode().into_iter().map(|t| (t.code, t.data)).collect()
+    }
+
+    /// Codes of the commands written so far
+    pub fn codes(&self) -> Vec<u8> {
+        self.decode().into_iter().map(|t| t.code).collect()
+    }
+
+    /// Time waited after each command before the next one was written
+    pub fn waits(&self) -> Vec<u32> {
+        self.decode().into_iter().map(|t| t.wait).collect()
+    }
+}
+
+pub struct MockPin {
+    bus: Bus,
+    pin: Pin,
+}
+
+impl embedded_hal::digital::v2::OutputPin for MockPin {
+    type Error = Infallible;
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.bus.log(Event::Pin(self.pin, false));
+        Ok(())
+    }
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.bus.log(Event::Pin(self.pin, true));
+        Ok(())
+    }
+}
+
+/// Input reading the queued levels, it stays at the last one
+pub struct MockInput(RefCell<VecDeque<bool>>);
+
+impl MockInput {
+    pub fn new(levels: &[bool]) -> Self {
+        Self(RefCell::new(levels.iter().copied().collect()))
+    }
+}
+
+impl embedded_hal::digital::v2::InputPin for MockInput {
+    type Error = Infallible;
+    fn is_high(&self) -> Result<bool, Infallible> {
+        let mut levels = self.0.borrow_mut();
+        if levels.len() > 1 {
+            return Ok(levels.pop_front().unwrap());
+        }
+        Ok(levels[0])
+    }
+    fn is_low(&self) -> Result<bool, Infallible> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+pub trait MockWord: BusWord {
+    fn to_u32(self) -> u32;
+    fn from_u32(value: u32) -> Self;
+}
+
+impl MockWord for u8 {
+    fn to_u32(self) -> u32 {
+        self.into()
+    }
+    fn from_u32(value: u32) -> Self {
+        value as u8
+    }
+}
+
+impl MockWord for u16 {
+    fn to_u32(self) -> u32 {
+        self.into()
+    }
+    fn from_u32(value: u32) -> Self {
+        value as u16
+    }
+}
+
+impl MockWord for u32 {
+    fn to_u32(self) -> u32 {
+        self
+    }
+    fn from_u32(value: u32) -> Self {
+        value
+    }
+}
+
+pub struct MockPort<W> {
+    bus: Bus,
+    word: PhantomData<W>,
+}
+
+impl<W: MockWord> WritePort for MockPort<W> {
+    type Word = W;
+    fn set_value(&mut self, value: W) {
+        self.bus.log(Event::Write(value.to_u32()));
+    }
+}
+
+impl<W: MockWord> ReadWritePort for MockPort<W> {
+    fn get_value(&mut self) -> W {
+        let value = self.bus.0.borrow_mut().reads.pop_front().unwrap_or(0);
+        self.bus.log(Event::Read(value));
+        W::from_u32(value)
+    }
+    fn dir_write(&mut self) {
+        self.bus.log(Event::DirWrite);
+    }
+    fn dir_read(&mut self) {
+        self.bus.log(Event::DirRead);
+    }
+}
+
+pub struct MockDelay {
+    bus: Bus,
+}
+
+impl embedded_hal::blocking::delay::DelayUs<u8> for MockDelay {
+    fn delay_us(&mut self, us: u8) {
+        self.bus.log(Event::Delay(us.into()));
+    }
+}
+
+pub type MockInterface = GpioReadWrite16BitInterface<MockPort<u16>, MockPin, MockPin, MockPin, NoPin<Infallible>, NoPin<Infallible>>;
+pub type MockDriver = Ssd1963<Lcd480x272, MockDelay, MockInterface>;
+
+pub fn interface(bus: &Bus) -> MockInterface {
+    GpioReadWrite16BitInterface::new(bus.port(), bus.pin(Pin::Dc), bus.pin(Pin::Wr), bus.pin(Pin::Rd))
+}
+
+/// An initialized driver on a read-write 16-bit interface, with the log cleared
+pub fn driver(bus: &Bus) -> MockDriver {
+    bus.queue_reads(&[PLL_LOCKED]);
+    let driver = Ssd1963::new(Lcd480x272, interface(bus), bus.delay()).unwrap();
+    bus.clear();
+    driver
+}
@@ -0,0 +1,90 @@
+use crate::commands::set_address_mode::{
+    ColorOrder, ColumnAddressOrder, DataLatchOrder, LineAddressOrder, PageAddressOrder, PageColumnOrder, SetAddressMode,
+};
+
+/// How far the panel is turned clockwise from its native position, the image is turned back so it stays upright
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rotation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+/// Logical orientation of the image, see `set_address_mode` (0x36)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Orientation {
+    pub rotation: Rotation,
+    /// Mirrors the image left to right after rotating it
+    pub mirror: bool,
+}
+
+impl Orientation {
+    pub const fn new(rotation: Rotation, mirror: bool) -> Self {
+        Self { rotation, mirror }
+    }
+
+    /// Logical columns run along the panel's rows and vice versa
+    pub const fn swaps_axes(&self) -> bool {
+        matches!(self.rotation, Rotation::Deg90 | Rotation::Deg270)
+    }
+
+    pub fn address_mode(&self) -> SetAddressMode {
+        let (page_reversed, column_reversed) = match self.rotation {
+            Rotation::Deg0 => (false, false),
+            Rotation::Deg90 => (false, true),
+            Rotation::Deg180 => (true, true),
+            Rotation::Deg270 => (true, false),
+        };
+        SetAddressMode {
+            page_address_order: if page_reversed {
+                PageAddressOrder::BottomToTop
+            } else {
+                PageAddressOrder::TopToBottom
+            },
+            column_address_order: if column_reversed != self.mirror {
+                ColumnAddressOrder::RightToLeft
+            } else {
+                ColumnAddressOrder::LeftToRight
+            },
+            page_column_order: if self.swaps_axes() {
+                PageColumnOrder::Reverse
+            } else {
+                PageColumnOrder::Normal
+            },
+            line_address_order: LineAddressOrder::LcdRefreshTopToBottom,
+            color_order: ColorOrder::Rgb,
+            data_latch_order: DataLatchOrder::LcdRefreshLeftToRight,
+            flip_horizontal: false,
+            flip_vertical: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packed_struct::PackedStruct;
+
+    #[test]
+    fn address_mode() {
+        let mode = |rotation, mirror| Orientation::new(rotation, mirror).address_mode().pack().unwrap();
+        assert_eq!(mode(Rotation::Deg0, false), [0b0000_0000]);
+        assert_eq!(mode(Rotation::Deg90, false), [0b0110_0000]);
+        assert_eq!(mode(Rotation::Deg180, false), [0b1100_0000]);
+        assert_eq!(mode(Rotation::Deg270, false), [0b1010_0000]);
+        assert_eq!(mode(Rotation::Deg0, true), [0b0100_0000]);
+        assert_eq!(mode(Rotation::Deg90, true), [0b0010_0000]);
+        assert_eq!(mode(Rotation::Deg180, true), [0b1000_0000]);
+        assert_eq!(mode(Rotation::Deg270, true), [0b1110_0000]);
+    }
+
+    #[test]
+    fn swaps_axes() {
+        assert!(!Orientation::new(Rotation::Deg0, false).swaps_axes());
+        assert!(Orientation::new(Rotation::Deg90, false).swaps_axes());
+        assert!(!Orientation::new(Rotation::Deg180, true).swaps_axes());
+        assert!(Orientation::new(Rotation::Deg270, true).swaps_axes());
+    }
+}
@@ -0,0 +1,227 @@
+use crate::commands::{SetHoriPeriod, SetLcdMode, SetVertPeriod};
+pub use crate::commands::set_lcd_mode::{Active, ColorSequence, Edge, TftPanelDataWidth, TftType};
+
+/// Horizontal sync timing, in the register units of `set_hori_period` (0xB4)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HorizontalTiming {
+    /// Horizontal total period (display + non-display) in pixel clocks
+    pub ht: u16,
+    /// Non-display period between the start of the horizontal sync (LLINE) and the first display data
+    pub hps: u16,
+    /// Horizontal sync pulse (LLINE) width in pixel clocks
+    pub hpw: u8,
+    /// Horizontal sync pulse (LLINE) start location in pixel clocks
+    pub lps: u16,
+    /// Horizontal sync pulse subpixel start position (serial TFT interface only)
+    pub lpspp: u8,
+}
+
+/// Vertical sync timing, in the register units of `set_vert_period` (0xB6)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerticalTiming {
+    /// Vertical total period (display + non-display) in lines
+    pub vt: u16,
+    /// Non-display period between the start of the vertical sync (LFRAME) and the first display line
+    pub vps: u16,
+    /// Vertical sync pulse (LFRAME) width in lines
+    pub vpw: u8,
+    /// Vertical sync pulse (LFRAME) start location in lines
+    pub fps: u16,
+}
+
+/// Reasons a `PanelConfig` is rejected before anything is sent to the controller
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimingError {
+    /// Width or height is zero or above the 2048 pixels the controller can address
+    ResolutionOutOfRange,
+    /// A horizontal value doesn't fit its register (11 bits, 7 bits for HPW, 2 bits for LPSPP)
+    HorizontalValueOutOfRange,
+    /// A vertical value doesn't fit its register (11 bits, 7 bits for VPW)
+    VerticalValueOutOfRange,
+    /// HT must be larger than HDP + HPS
+    HorizontalTotalTooShort,
+    /// VT must be larger than VDP + VPS
+    VerticalTotalTooShort,
+    /// The HPW + 1 pixel clocks of the sync pulse must fit within the HT - HDP non-display period
+    HorizontalPulseTooWide,
+    /// The VPW + 1 lines of the sync pulse must fit within the VT - VDP non-display period
+    VerticalPulseTooWide,
+    /// LPS + HPW must fit within HT
+    HorizontalPulseOutsideLine,
+    /// FPS + VPW must fit within VT
+    VerticalPulseOutsideFrame,
+}
+
+const MAX_11BIT: u16 = 0x7FF;
+const MAX_7BIT: u8 = 0x7F;
+
+/// Everything the controller needs to know to drive a particular TFT panel, apart from the pixel clock which is part of `Clocks`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PanelConfig {
+    pub horizontal: HorizontalTiming,
+    pub vertical: VerticalTiming,
+    pub lshift_polarity: Edge,
+    pub lline_polarity: Active,
+    pub lframe_polarity: Active,
+    pub tft_type: TftType,
+    pub data_width: TftPanelDataWidth,
+    pub even_line_color_sequence: ColorSequence,
+    pub odd_line_color_sequence: ColorSequence,
+}
+
+impl PanelConfig {
+    /// 24-bit TFT mode 0 panel with active low sync signals latching data on the falling LSHIFT edge
+    pub const fn new(horizontal: HorizontalTiming, vertical: VerticalTiming) -> Self {
+        Self {
+            horizontal,
+            vertical,
+            lshift_polarity: Edge::Falling,
+            lline_polarity: Active::ActiveLow,
+            lframe_polarity: Active::ActiveLow,
+            tft_type: TftType::TftMode0,
+            data_width: TftPanelDataWidth::B24,
+            even_line_color_sequence: ColorSequence::Rgb,
+            odd_line_color_sequence: ColorSequence::Rgb,
+        }
+    }
+
+    /// Checks that the timing is consistent for a `width` x `height` panel
+    pub fn validate(&self, width: u16, height: u16) -> Result<(), TimingError> {
+        if width == 0 || height == 0 || width > MAX_11BIT + 1 || height > MAX_11BIT + 1 {
+            return Err(TimingError::ResolutionOutOfRange);
+        }
+        let hdp = width - 1;
+        let vdp = height - 1;
+        let h = &self.horizontal;
+        let v = &self.vertical;
+        if h.ht > MAX_11BIT || h.hps > MAX_11BIT || h.hpw > MAX_7BIT || h.lps > MAX_11BIT || h.lpspp > 0b11 {
+            return Err(TimingError::HorizontalValueOutOfRange);
+        }
+        if v.vt > MAX_11BIT || v.vps > MAX_11BIT || v.vpw > MAX_7BIT || v.fps > MAX_11BIT {
+            return Err(TimingError::VerticalValueOutOfRange);
+        }
+        if h.ht <= hdp + h.hps {
+            return Err(TimingError::HorizontalTotalTooShort);
+        }
+        if v.vt <= vdp + v.vps {
+            return Err(TimingError::VerticalTotalTooShort);
+        }
+        // HT and HPW count one less than the pixel clocks they stand for, HDP one less than the width
+        if u16::from(h.hpw) + 1 > h.ht - hdp {
+            return Err(TimingError::HorizontalPulseTooWide);
+        }
+        if u16::from(v.vpw) + 1 > v.vt - vdp {
+            return Err(TimingError::VerticalPulseTooWide);
+        }
+        if h.lps + u16::from(h.hpw) > h.ht {
+            return Err(TimingError::HorizontalPulseOutsideLine);
+        }
+        if v.fps + u16::from(v.vpw) > v.vt {
+            return Err(TimingError::VerticalPulseOutsideFrame);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn lcd_mode(&self, width: u16, height: u16) -> SetLcdMode {
+        SetLcdMode {
+            data_width: self.data_width,
+            color_depth_enhancement_enable: false,
+            frc_enable: false,
+            lshift_polarity: self.lshift_polarity,
+            lline_polarity: self.lline_polarity,
+            lframe_polarity: self.lframe_polarity,
+            tft_type: self.tft_type,
+            hdp: (width - 1).into(),
+            vdp: (height - 1).into(),
+            even_line_color_sequence: self.even_line_color_sequence,
+            odd_line_color_sequence: self.odd_line_color_sequence,
+        }
+    }
+
+    pub(crate) fn hori_period(&self) -> SetHoriPeriod {
+        let h = &self.horizontal;
+        SetHoriPeriod {
+            ht: h.ht.into(),
+            hps: h.hps.into(),
+            hpw: h.hpw.into(),
+            lps: h.lps.into(),
+            lpspp: h.lpspp.into(),
+        }
+    }
+
+    pub(crate) fn vert_period(&self) -> SetVertPeriod {
+        let v = &self.vertical;
+        SetVertPeriod {
+            vt: v.vt.into(),
+            vps: v.vps.into(),
+            vpw: v.vpw.into(),
+            fps: v.fps.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CONFIG: PanelConfig = PanelConfig::new(
+        HorizontalTiming {
+            ht: 928,
+            hps: 46,
+            hpw: 48,
+            lps: 15,
+            lpspp: 0,
+        },
+        VerticalTiming {
+            vt: 525,
+            vps: 16,
+            vpw: 16,
+            fps: 8,
+        },
+    );
+
+    #[test]
+    fn periods() {
+        let hp = CONFIG.hori_period();
+        assert_eq!((*hp.ht, *hp.hps, *hp.hpw, *hp.lps, *hp.lpspp), (928, 46, 48, 15, 0));
+        let vp = CONFIG.vert_period();
+        assert_eq!((*vp.vt, *vp.vps, *vp.vpw, *vp.fps), (525, 16, 16, 8));
+        let lm = CONFIG.lcd_mode(800, 480);
+        assert_eq!((*lm.hdp, *lm.vdp), (799, 479));
+    }
+
+    #[test]
+    fn validate() {
+        assert_eq!(CONFIG.validate(800, 480), Ok(()));
+        assert_eq!(CONFIG.validate(0, 480), Err(TimingError::ResolutionOutOfRange));
+        assert_eq!(CONFIG.validate(2049, 480), Err(TimingError::ResolutionOutOfRange));
+        assert_eq!(CONFIG.validate(883, 480), Err(TimingError::HorizontalTotalTooShort));
+        assert_eq!(CONFIG.validate(800, 510), Err(TimingError::VerticalTotalTooShort));
+
+        let mut c = CONFIG;
+        c.horizontal.ht = 0x800;
+        assert_eq!(c.validate(800, 480), Err(TimingError::HorizontalValueOutOfRange));
+        let mut c = CONFIG;
+        c.horizontal.hpw = 0x80;
+        assert_eq!(c.validate(800, 480), Err(TimingError::HorizontalValueOutOfRange));
+        let mut c = CONFIG;
+        c.vertical.fps = 0x800;
+        assert_eq!(c.validate(800, 480), Err(TimingError::VerticalValueOutOfRange));
+        let mut c = CONFIG;
+        c.horizontal.hpw = 78;
+        assert_eq!(c.validate(850, 480), Ok(()));
+        c.horizontal.hpw = 79;
+        assert_eq!(c.validate(850, 480), Err(TimingError::HorizontalPulseTooWide));
+        let mut c = CONFIG;
+        c.vertical.vpw = 45;
+        assert_eq!(c.validate(800, 480), Ok(()));
+        c.vertical.vpw = 46;
+        assert_eq!(c.validate(800, 480), Err(TimingError::VerticalPulseTooWide));
+        let mut c = CONFIG;
+        c.horizontal.lps = 900;
+        assert_eq!(c.validate(800, 480), Err(TimingError::HorizontalPulseOutsideLine));
+        let mut c = CONFIG;
+        c.vertical.fps = 510;
+        assert_eq!(c.validate(800, 480), Err(TimingError::VerticalPulseOutsideFrame));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::color::{Rgb565, Rgb666, Rgb888};
+use crate::interface::BusWord;
+pub use crate::commands::set_pixel_data_interface::PixelDataInterfaceFormat;
+
+/// How pixels travel over the host data bus, see `set_pixel_data_interface` (0xF0)
+///
+/// Pixels are transferred in groups, most formats use groups of a single pixel,
+/// `B16Packed` fits 2 pixels into 3 words.
+pub trait PixelFormat {
+    type Word: BusWord;
+    type Color: Copy + Default;
+    const FORMAT: PixelDataInterfaceFormat;
+    /// Pixels of one group
+    type Pixels: AsRef<[Self::Color]> + AsMut<[Self::Color]> + Default;
+    /// Words carrying one group
+    type Words: AsRef<[Self::Word]> + AsMut<[Self::Word]> + Default;
+
+    fn pack(pixels: &Self::Pixels) -> Self::Words;
+    fn unpack(words: &Self::Words) -> Self::Pixels;
+
+    /// Words needed to transfer only the first `pixels` pixels of a group
+    fn words(pixels: usize) -> usize {
+        let group_pixels = Self::Pixels::default().as_ref().len();
+        let group_words = Self::Words::default().as_ref().len();
+        (pixels * group_words).div_ceil(group_pixels)
+    }
+}
+
+/// 8-bit bus, a byte per component
+pub struct B8;
+impl PixelFormat for B8 {
+    type Word = u8;
+    type Color = Rgb888;
+    const FORMAT: PixelDataInterfaceFormat = PixelDataInterfaceFormat::B8;
+    type Pixels = [Rgb888; 1];
+    type Words = [u8; 3];
+
+    fn pack([c]: &[Rgb888; 1]) -> [u8; 3] {
+        [c.r, c.g, c.b]
+    }
+    fn unpack(&[r, g, b]: &[u8; 3]) -> [Rgb888; 1] {
+        [Rgb888 { r, g, b }]
+    }
+}
+
+/// 16-bit bus, a 565 pixel per word
+pub struct B16Format565;
+impl PixelFormat for B16Format565 {
+    type Word = u16;
+    type Color = Rgb565;
+    const FORMAT: PixelDataInterfaceFormat = PixelDataInterfaceFormat::B16Format565;
+    type Pixels = [Rgb565; 1];
+    type Words = [u16; 1];
+
+    fn pack([c]: &[Rgb565; 1]) -> [u16; 1] {
+        [c.0]
+    }
+    fn unpack(&[w]: &[u16; 1]) -> [Rgb565; 1] {
+        [Rgb565(w)]
+    }
+}
+
+/// 16-bit bus, 2 full color pixels in 3 words
+pub struct B16Packed;
+impl PixelFormat for B16Packed {
+    type Word = u16;
+    type Color = Rgb888;
+    const FORMAT: PixelDataInterfaceFormat = PixelDataInterfaceFormat::B16Packed;
+    type Pixels = [Rgb888; 2];
+    type Words = [u16; 3];
+
+    fn pack([c1, c2]: &[Rgb888; 2]) -> [u16; 3] {
+        [
+            u16::from_be_bytes([c1.r, c1.g]),
+            u16::from_be_bytes([c1.b, c2.r]),
+            u16::from_be_bytes([c2.g, c2.b]),
+        ]
+    }
+    fn unpack(&[w1, w2, w3]: &[u16; 3]) -> [Rgb888; 2] {
+        let [r1, g1] = w1.to_be_bytes();
+        let [b1, r2] = w2.to_be_bytes();
+        let [g2, b2] = w3.to_be_bytes();
+        [Rgb888::new(r1, g1, b1), Rgb888::new(r2, g2, b2)]
+    }
+}
+
+/// 18-bit bus, a 666 pixel per word
+pub struct B18;
+impl PixelFormat for B18 {
+    type Word = u32;
+    type Color = Rgb666;
+    const FORMAT: PixelDataInterfaceFormat = PixelDataInterfaceFormat::B18;
+    type Pixels = [Rgb666; 1];
+    type Words = [u32; 1];
+
+    fn pack([c]: &[Rgb666; 1]) -> [u32; 1] {
+        [u32::from(c.r & 0x3F) << 12 | u32::from(c.g & 0x3F) << 6 | u32::from(c.b & 0x3F)]
+    }
+    fn unpack(&[w]: &[u32; 1]) -> [Rgb666; 1] {
+        [Rgb666 {
+            r: (w >> 12) as u8 & 0x3F,
+            g: (w >> 6) as u8 & 0x3F,
+            b: w as u8 & 0x3F,
+        }]
+    }
+}
+
+/// 24-bit bus, a 888 pixel per word
+pub struct B24;
+impl PixelFormat for B24 {
+    type Word = u32;
+    type Color = Rgb888;
+    const FORMAT: PixelDataInterfaceFormat = PixelDataInterfaceFormat::B24;
+    type Pixels = [Rgb888; 1];
+    type Words = [u32; 1];
+
+    fn pack([c]: &[Rgb888; 1]) -> [u32; 1] {
+        [u32::from_be_bytes([0, c.r, c.g, c.b])]
+    }
+    fn unpack(&[w]: &[u32; 1]) -> [Rgb888; 1] {
+        let [_, r, g, b] = w.to_be_bytes();
+        [Rgb888 { r, g, b }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pack() {
+        let red = Rgb888::new(0xFF, 0, 0);
+        let grey = Rgb888::new(0x12, 0x34, 0x56);
+        assert_eq!(B8::pack(&[grey]), [0x12, 0x34, 0x56]);
+        assert_eq!(B16Format565::pack(&[Rgb565(0xF81F)]), [0xF81F]);
+        assert_eq!(B16Packed::pack(&[grey, red]), [0x1234, 0x56FF, 0x0000]);
+        assert_eq!(B18::pack(&[Rgb666 { r: 0x3F, g: 0, b: 1 }]), [0x3F001]);
+        assert_eq!(B24::pack(&[grey]), [0x123456]);
+    }
+
+    #[test]
+    fn unpack() {
+        let grey = Rgb888::new(0x12, 0x34, 0x56);
+        let white = Rgb888::new(0xFF, 0xFF, 0xFF);
+        assert_eq!(B8::unpack(&B8::pack(&[grey])), [grey]);
+        assert_eq!(B16Packed::unpack(&B16Packed::pack(&[grey, white])), [grey, white]);
+        let c = Rgb666 { r: 1, g: 2, b: 3 };
+        assert_eq!(B18::unpack(&B18::pack(&[c])), [c]);
+        assert_eq!(B24::unpack(&B24::pack(&[white])), [white]);
+    }
+
+    #[test]
+    fn words() {
+        assert_eq!(B8::words(1), 3);
+        assert_eq!(B16Format565::words(1), 1);
+        assert_eq!(B16Packed::words(1), 2);
+        assert_eq!(B16Packed::words(2), 3);
+        assert_eq!(B24::words(1), 1);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::commands::{SetLShiftFreq, SetPllMn};
+use crate::panel::PanelConfig;
+
+const VCO_MIN: u64 = 250_000_000;
+const VCO_MAX: u64 = 800_000_000;
+/// Highest system clock the controller is specified for
+pub const SYSTEM_CLOCK_MAX: u32 = 110_000_000;
+/// Minimum time the datasheet requires between enabling the PLL and using it as system clock
+pub const PLL_LOCK_TIME_US: u32 = 100;
+/// How long a read-write interface polls `get_pll_status` before giving up
+pub const PLL_LOCK_TIMEOUT_US: u32 = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockError {
+    /// The requested system clock is above `SYSTEM_CLOCK_MAX`
+    SystemClockTooHigh,
+    /// No multiplier/divider pair keeps the VCO within 250MHz..=800MHz
+    VcoOutOfRange,
+    /// The pixel clock is zero or higher than the system clock
+    PixelClockOutOfRange,
+    /// The reference clock or the system clock is zero
+    ZeroClock,
+}
+
+/// PLL and pixel clock settings
+///
+/// ```text
+/// VCO          = reference clock * (M + 1)
+/// system clock = VCO / (N + 1)
+/// pixel clock  = system clock * (LCDC_FPR + 1) / 2^20
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Clocks {
+    /// Crystal or external reference clock frequency in Hz
+    pub reference_clock: u32,
+    /// PLL multiplier M
+    pub multiplier: u8,
+    /// PLL divider N
+    pub divider: u8,
+    /// Pixel clock fraction LCDC_FPR
+    pub lcdc_fpr: u32,
+    /// Time waited for the PLL to lock on write-only interfaces
+    pub lock_wait_us: u32,
+    /// Time the PLL lock status is polled for on read-write interfaces
+    pub lock_timeout_us: u32,
+}
+
+impl Clocks {
+    /// Picks the multiplier and divider giving the system clock closest to `system_clock`
+    /// and the LCDC_FPR giving the pixel clock closest to `pixel_clock`, all frequencies in Hz
+    pub const fn new(reference_clock: u32, system_clock: u32, pixel_clock: u32) -> Result<Self, ClockError> {
+        if reference_clock == 0 || system_clock == 0 {
+            return Err(ClockError::ZeroClock);
+        }
+        if system_clock > SYSTEM_CLOCK_MAX {
+            return Err(ClockError::SystemClockTooHigh);
+        }
+        let reference = reference_clock as u64;
+        let target = system_clock as u64;
+        let mut best: Option<(u8, u8, u64)> = None;
+        let mut n = 0;
+        while n <= 31 {
+            // round to the nearest multiplier
+            let m1 = (target * (n + 1) + reference / 2) / reference;
+            let vco = reference * m1;
+            if m1 >= 1 && m1 <= 256 && vco >= VCO_MIN && vco <= VCO_MAX && vco / (n + 1) <= SYSTEM_CLOCK_MAX as u64 {
+                let achieved = vco / (n + 1);
+                let error = achieved.abs_diff(target);
+                let better = match best {
+                    Some((_, _, best_error)) => error < best_error,
+                    None => true,
+                };
+                if better {
+                    best = Some(((m1 - 1) as u8, n as u8, error));
+                }
+            }
+            n += 1;
+        }
+        let (multiplier, divider) = match best {
+            Some((m, n, _)) => (m, n),
+            None => return Err(ClockError::VcoOutOfRange),
+        };
+        let achieved = reference * (multiplier as u64 + 1) / (divider as u64 + 1);
+        let fpr1 = (((pixel_clock as u64) << 20) + achieved / 2) / achieved;
+        if fpr1 == 0 || fpr1 > 1 << 20 {
+            return Err(ClockError::PixelClockOutOfRange);
+        }
+        Ok(Self {
+            reference_clock,
+            multiplier,
+            divider,
+            lcdc_fpr: (fpr1 - 1) as u32,
+            lock_wait_us: PLL_LOCK_TIME_US,
+            lock_timeout_us: PLL_LOCK_TIMEOUT_US,
+        })
+    }
+
+    pub const fn vco_frequency(&self) -> u32 {
+        self.reference_clock * (self.multiplier as u32 + 1)
+    }
+
+    /// Achieved system (PLL output) clock in Hz
+    pub const fn system_clock(&self) -> u32 {
+        self.vco_frequency() / (self.divider as u32 + 1)
+    }
+
+    /// Achieved pixel clock in Hz
+    pub const fn pixel_clock(&self) -> u32 {
+        ((self.system_clock() as u64 * (self.lcdc_fpr as u64 + 1)) >> 20) as u32
+    }
+
+    /// Achieved frame rate in mHz (1/1000 Hz) for the given panel timing
+    pub const fn refresh_rate_millihertz(&self, config: &PanelConfig) -> u32 {
+        let frame = config.horizontal.ht as u64 * config.vertical.vt as u64;
+        if frame == 0 {
+            return 0;
+        }
+        (self.pixel_clock() as u64 * 1000 / frame) as u32
+    }
+
+    pub(crate) fn pll_mn(&self) -> SetPllMn {
+        SetPllMn {
+            pll_multiplier: self.multiplier,
+            pll_divider: self.divider.into(),
+            use_multiplier_and_divider: true,
+        }
+    }
+
+    pub(crate) fn lshift_freq(&self) -> SetLShiftFreq {
+        SetLShiftFreq {
+            lcdc_fpr: self.lcdc_fpr.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::panel::{HorizontalTiming, VerticalTiming};
+
+    #[test]
+    fn pll() {
+        const CLOCKS: Result<Clocks, ClockError> = Clocks::new(10_000_000, 100_000_000, 30_000_000);
+        let clocks = CLOCKS.unwrap();
+        assert_eq!(clocks.system_clock(), 100_000_000);
+        assert!((250_000_000..=800_000_000).contains(&clocks.vco_frequency()));
+        assert_eq!(clocks.vco_frequency(), 10_000_000 * (u32::from(clocks.multiplier) + 1));
+        assert_eq!(clocks.lcdc_fpr, 314_572);
+        assert_eq!(clocks.pixel_clock(), 30_000_019);
+
+        let clocks = Clocks::new(12_000_000, 110_000_000, 9_000_000).unwrap();
+        assert_eq!(clocks.system_clock(), 110_000_000);
+    }
+
+    #[test]
+    fn lshift_freq() {
+        // datasheet example, PCLK = 5.3MHz with PLL Frequency = 100MHz
+        let clocks = Clocks::new(10_000_000, 100_000_000, 5_300_000).unwrap();
+        assert_eq!(clocks.lshift_freq().lcdc_fpr, 55_574.into());
+        let clocks = Clocks::new(10_000_000, 100_000_000, 100_000_000).unwrap();
+        assert_eq!(clocks.lshift_freq().lcdc_fpr, 0xF_FFFF.into());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Clocks::new(10_000_000, 120_000_000, 30_000_000), Err(ClockError::SystemClockTooHigh));
+        assert_eq!(Clocks::new(1_000_000, 100_000_000, 30_000_000), Err(ClockError::VcoOutOfRange));
+        assert_eq!(Clocks::new(10_000_000, 100_000_000, 0), Err(ClockError::PixelClockOutOfRange));
+        assert_eq!(Clocks::new(10_000_000, 100_000_000, 101_000_000), Err(ClockError::PixelClockOutOfRange));
+        assert_eq!(Clocks::new(0, 100_000_000, 30_000_000), Err(ClockError::ZeroClock));
+        assert_eq!(Clocks::new(10_000_000, 0, 30_000_000), Err(ClockError::ZeroClock));
+        const ZERO_REFERENCE: Result<Clocks, ClockError> = Clocks::new(0, 100_000_000, 30_000_000);
+        assert!(ZERO_REFERENCE.is_err());
+    }
+
+    #[test]
+    fn refresh_rate() {
+        let config = PanelConfig::new(
+            HorizontalTiming {
+                ht: 1000,
+                hps: 46,
+                hpw: 20,
+                lps: 0,
+                lpspp: 0,
+            },
+            VerticalTiming {
+                vt: 500,
+                vps: 16,
+                vpw: 8,
+                fps: 0,
+            },
+        );
+        let clocks = Clocks::new(10_000_000, 100_000_000, 30_000_000).unwrap();
+        assert_eq!(clocks.refresh_rate_millihertz(&config), 60_000);
+    }
+}
@@ -0,0 +1,36 @@
+/// Power state of the controller, see `Ssd1963::set_power_state`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PowerState {
+    /// Display on, full color
+    #[default]
+    Normal,
+    /// Display blanked, the frame buffer can still be drawn to
+    DisplayOff,
+    /// Display on with 8 colors, see `enter_idle_mode` (0x39)
+    Idle,
+    /// Panel and display off, see `enter_sleep_mode` (0x10)
+    Sleep,
+    /// Like `Sleep`, with the PLL stopped too, see `set_deep_sleep` (0xE5)
+    DeepSleep,
+}
+
+impl PowerState {
+    /// The frame buffer can't be accessed
+    pub const fn is_asleep(&self) -> bool {
+        matches!(self, PowerState::Sleep | PowerState::DeepSleep)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn is_asleep() {
+        assert!(!PowerState::default().is_asleep());
+        assert!(!PowerState::DisplayOff.is_asleep());
+        assert!(!PowerState::Idle.is_asleep());
+        assert!(PowerState::Sleep.is_asleep());
+        assert!(PowerState::DeepSleep.is_asleep());
+    }
+}
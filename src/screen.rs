@@ -0,0 +1,131 @@
+use crate::panel::{Active, ColorSequence, Edge, HorizontalTiming, PanelConfig, TftPanelDataWidth, TftType, VerticalTiming};
+
+/// Describes a TFT panel attached to the controller
+///
+/// Only the geometry, the pixel clock and the sync timings are mandatory,
+/// the remaining constants default to a 24-bit panel with active low sync signals.
+pub trait Screen {
+    const WIDTH: u16;
+    const HEIGHT: u16;
+    /// Pixel clock (LSHIFT) frequency in Hz
+    const PIXEL_CLOCK: u32;
+    const HORIZONTAL: HorizontalTiming;
+    const VERTICAL: VerticalTiming;
+    const LSHIFT_POLARITY: Edge = Edge::Falling;
+    const LLINE_POLARITY: Active = Active::ActiveLow;
+    const LFRAME_POLARITY: Active = Active::ActiveLow;
+    const DATA_WIDTH: TftPanelDataWidth = TftPanelDataWidth::B24;
+
+    const CONFIG: PanelConfig = PanelConfig {
+        horizontal: Self::HORIZONTAL,
+        vertical: Self::VERTICAL,
+        lshift_polarity: Self::LSHIFT_POLARITY,
+        lline_polarity: Self::LLINE_POLARITY,
+        lframe_polarity: Self::LFRAME_POLARITY,
+        tft_type: TftType::TftMode0,
+        data_width: Self::DATA_WIDTH,
+        even_line_color_sequence: ColorSequence::Rgb,
+        odd_line_color_sequence: ColorSequence::Rgb,
+    };
+}
+
+/// 4.3" 480x272 panel
+pub struct Lcd480x272;
+impl Screen for Lcd480x272 {
+    const WIDTH: u16 = 480;
+    const HEIGHT: u16 = 272;
+    const PIXEL_CLOCK: u32 = 9_000_000;
+    const HORIZONTAL: HorizontalTiming = HorizontalTiming {
+        ht: 531,
+        hps: 43,
+        hpw: 10,
+        lps: 8,
+        lpspp: 0,
+    };
+    const VERTICAL: VerticalTiming = VerticalTiming {
+        vt: 288,
+        vps: 12,
+        vpw: 10,
+        fps: 4,
+    };
+}
+
+/// 5.7" 640x480 panel with VGA timing
+pub struct Lcd640x480;
+impl Screen for Lcd640x480 {
+    const WIDTH: u16 = 640;
+    const HEIGHT: u16 = 480;
+    const PIXEL_CLOCK: u32 = 25_175_000;
+    const HORIZONTAL: HorizontalTiming = HorizontalTiming {
+        ht: 800,
+        hps: 144,
+        hpw: 96,
+        lps: 0,
+        lpspp: 0,
+    };
+    const VERTICAL: VerticalTiming = VerticalTiming {
+        vt: 525,
+        vps: 35,
+        vpw: 2,
+        fps: 0,
+    };
+}
+
+/// 5" and 7" 800x480 panels
+pub struct Lcd800x480;
+impl Screen for Lcd800x480 {
+    const WIDTH: u16 = 800;
+    const HEIGHT: u16 = 480;
+    const PIXEL_CLOCK: u32 = 30_000_000;
+    const HORIZONTAL: HorizontalTiming = HorizontalTiming {
+        ht: 928,
+        hps: 46,
+        hpw: 48,
+        lps: 15,
+        lpspp: 0,
+    };
+    const VERTICAL: VerticalTiming = VerticalTiming {
+        vt: 525,
+        vps: 16,
+        vpw: 16,
+        fps: 8,
+    };
+}
+
+/// 5" 864x480 panel
+pub struct Lcd864x480;
+impl Screen for Lcd864x480 {
+    const WIDTH: u16 = 864;
+    const HEIGHT: u16 = 480;
+    const PIXEL_CLOCK: u32 = 33_000_000;
+    const HORIZONTAL: HorizontalTiming = HorizontalTiming {
+        ht: 1050,
+        hps: 88,
+        hpw: 20,
+        lps: 0,
+        lpspp: 0,
+    };
+    const VERTICAL: VerticalTiming = VerticalTiming {
+        vt: 525,
+        vps: 32,
+        vpw: 3,
+        fps: 0,
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn validate<S: Screen>() {
+        assert_eq!(S::CONFIG.validate(S::WIDTH, S::HEIGHT), Ok(()));
+    }
+
+    #[test]
+    fn presets() {
+        validate::<Lcd480x272>();
+        validate::<Lcd640x480>();
+        validate::<Lcd800x480>();
+        validate::<Lcd864x480>();
+    }
+}
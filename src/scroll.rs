@@ -0,0 +1,119 @@
+use crate::commands::{SetScrollArea, SetScrollStart};
+
+/// Reasons a scroll request is rejected before anything is sent to the controller
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollError {
+    /// The fixed areas and the scrolling area must add up to the screen height, the scrolling area can't be empty
+    InvalidRegion,
+    /// The scroll position must be within the scrolling area
+    LineOutOfRange,
+    /// Double buffering flips frames through the scroll start address, so scrolling is unavailable meanwhile
+    DoubleBuffering,
+}
+
+/// Split of the screen into a fixed top area, a vertically scrolling area and a fixed bottom area,
+/// see `set_scroll_area` (0x33)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollRegion {
+    /// Lines at the top of the screen that don't scroll
+    pub top_fixed: u16,
+    /// Lines that scroll
+    pub height: u16,
+    /// Lines at the bottom of the screen that don't scroll
+    pub bottom_fixed: u16,
+}
+
+impl ScrollRegion {
+    pub fn new(top_fixed: u16, height: u16, bottom_fixed: u16, screen_height: u16) -> Result<Self, ScrollError> {
+        let total = u32::from(top_fixed) + u32::from(height) + u32::from(bottom_fixed);
+        if height == 0 || total != u32::from(screen_height) {
+            return Err(ScrollError::InvalidRegion);
+        }
+        Ok(Self {
+            top_fixed,
+            height,
+            bottom_fixed,
+        })
+    }
+
+    /// The whole screen scrolls
+    pub const fn full(screen_height: u16) -> Self {
+        Self {
+            top_fixed: 0,
+            height: screen_height,
+            bottom_fixed: 0,
+        }
+    }
+
+    /// Frame memory row shown on screen row `y` while the scrolling area is scrolled by `offset` lines
+    ///
+    /// Rows in the fixed areas map to themselves.
+    pub fn map_row(&self, offset: u16, y: u16) -> u16 {
+        match y.checked_sub(self.top_fixed) {
+            Some(row) if row < self.height => self.top_fixed + ((u32::from(row) + u32::from(offset)) % u32::from(self.height)) as u16,
+            _ => y,
+        }
+    }
+
+    /// Scroll offset reached by moving `lines` lines from `offset`, wrapping around the scrolling area
+    pub fn offset_by(&self, offset: u16, lines: i16) -> u16 {
+        (i32::from(offset) + i32::from(lines)).rem_euclid(i32::from(self.height)) as u16
+    }
+
+    pub(crate) fn area(&self) -> SetScrollArea {
+        SetScrollArea {
+            top_fixed_area: self.top_fixed,
+            vertical_scroll_area: self.height,
+            bottom_fixed_area: self.bottom_fixed,
+        }
+    }
+
+    pub(crate) fn start(&self, offset: u16) -> SetScrollStart {
+        SetScrollStart {
+            source_line: self.top_fixed + offset,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        assert_eq!(
+            ScrollRegion::new(10, 250, 12, 272),
+            Ok(ScrollRegion {
+                top_fixed: 10,
+                height: 250,
+                bottom_fixed: 12
+            })
+        );
+        assert_eq!(ScrollRegion::new(0, 272, 0, 272), Ok(ScrollRegion::full(272)));
+        assert_eq!(ScrollRegion::new(10, 250, 10, 272), Err(ScrollError::InvalidRegion));
+        assert_eq!(ScrollRegion::new(272, 0, 0, 272), Err(ScrollError::InvalidRegion));
+        assert_eq!(ScrollRegion::new(u16::MAX, u16::MAX, 2, 0), Err(ScrollError::InvalidRegion));
+    }
+
+    #[test]
+    fn map_row() {
+        let region = ScrollRegion::new(10, 100, 20, 130).unwrap();
+        assert_eq!(region.map_row(30, 5), 5);
+        assert_eq!(region.map_row(30, 125), 125);
+        assert_eq!(region.map_row(0, 10), 10);
+        assert_eq!(region.map_row(30, 10), 40);
+        assert_eq!(region.map_row(30, 79), 109);
+        assert_eq!(region.map_row(30, 80), 10);
+        assert_eq!(region.map_row(99, 109), 108);
+    }
+
+    #[test]
+    fn offset_by() {
+        let region = ScrollRegion::new(10, 100, 20, 130).unwrap();
+        assert_eq!(region.offset_by(0, 5), 5);
+        assert_eq!(region.offset_by(95, 10), 5);
+        assert_eq!(region.offset_by(5, -10), 95);
+        assert_eq!(region.offset_by(0, -1000), 0);
+        assert_eq!(region.start(5).source_line, 15);
+    }
+}